@@ -0,0 +1,140 @@
+//! Fetching a range of days at once: which days to fetch, what became of each
+//! and a table summing it up.
+
+use std::{fmt, ops::RangeInclusive, str::FromStr};
+
+/// The days given on the command line: one, or a range such as `1..=10` or
+/// `1..11`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Days {
+    One(u8),
+    Range(RangeInclusive<u8>),
+}
+
+impl Days {
+    pub fn all() -> Self {
+        Days::Range(1..=25)
+    }
+}
+
+impl FromStr for Days {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let day = |s: &str| {
+            let day: u8 = s.parse().map_err(|_| format!("`{s}` isn't a day"))?;
+            if !(1..=25).contains(&day) {
+                return Err(format!("there is no day {day}"));
+            }
+            Ok(day)
+        };
+        let Some((start, end)) = s.split_once("..") else {
+            return day(s).map(Days::One);
+        };
+        let start = day(start)?;
+        let end = match end.strip_prefix('=') {
+            Some(end) => day(end)?,
+            None => day(end)?
+                .checked_sub(1)
+                .ok_or_else(|| format!("`{s}` has no days in it"))?,
+        };
+        if start > end {
+            return Err(format!("`{s}` has no days in it"));
+        }
+        Ok(Days::Range(start..=end))
+    }
+}
+
+/// What became of fetching a day.
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    /// Not fetched, as it hasn't unlocked yet.
+    Skipped(String),
+    /// Not fetched, as it already had been.
+    Cached,
+    Fetched,
+    Failed(String),
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::Skipped(_) => write!(f, "skipped"),
+            Status::Cached => write!(f, "cached"),
+            Status::Fetched => write!(f, "fetched"),
+            Status::Failed(_) => write!(f, "failed"),
+        }
+    }
+}
+
+/// A row per day with its status and why, then a count of each status.
+pub fn summary(statuses: &[(u8, Status)]) -> Vec<String> {
+    let row = |day: &str, status: &str, note: &str| {
+        format!("{day:<5} {status:<8} {note}")
+            .trim_end()
+            .to_string()
+    };
+    let mut rows = vec![row("day", "status", "")];
+    for (day, status) in statuses {
+        let note = match status {
+            Status::Skipped(note) | Status::Failed(note) => note,
+            Status::Cached | Status::Fetched => "",
+        };
+        rows.push(row(&day.to_string(), &status.to_string(), note));
+    }
+    let counts: Vec<String> = ["fetched", "cached", "skipped", "failed"]
+        .into_iter()
+        .map(|name| {
+            let count = statuses
+                .iter()
+                .filter(|(_, status)| status.to_string() == name)
+                .count();
+            format!("{count} {name}")
+        })
+        .collect();
+    rows.push(counts.join(", "));
+    rows
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing() {
+        assert_eq!("7".parse(), Ok(Days::One(7)));
+        assert_eq!("1..=10".parse(), Ok(Days::Range(1..=10)));
+        assert_eq!("3..6".parse(), Ok(Days::Range(3..=5)));
+        assert_eq!("0".parse::<Days>(), Err("there is no day 0".to_string()));
+        assert_eq!(
+            "5..5".parse::<Days>(),
+            Err("`5..5` has no days in it".to_string())
+        );
+        assert_eq!("1..=x".parse::<Days>(), Err("`x` isn't a day".to_string()));
+        assert_eq!(
+            "1..=26".parse::<Days>(),
+            Err("there is no day 26".to_string())
+        );
+    }
+
+    #[test]
+    fn summaries() {
+        let statuses = [
+            (1, Status::Cached),
+            (2, Status::Fetched),
+            (
+                3,
+                Status::Failed("input returned 500: the request failed".into()),
+            ),
+            (4, Status::Skipped("unlocks in 03:00:00".into())),
+        ];
+        insta::assert_snapshot!(summary(&statuses).join("\n"), @r"
+        day   status
+        1     cached
+        2     fetched
+        3     failed   input returned 500: the request failed
+        4     skipped  unlocks in 03:00:00
+        1 fetched, 1 cached, 1 skipped, 1 failed
+        ");
+    }
+}
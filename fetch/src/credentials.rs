@@ -0,0 +1,166 @@
+//! Where the session cookie comes from: the environment, or otherwise a file
+//! in the user's config directory that only they can read.
+
+use std::{
+    env,
+    fmt::{self, Display},
+    fs,
+    path::{Path, PathBuf},
+};
+
+pub const SESSION_COOKIE_ENV_VAR: &str = "AOC_SESSION_COOKIE";
+
+#[derive(Debug, PartialEq)]
+pub enum Source {
+    Env,
+    File(PathBuf),
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Source::Env => write!(f, "${SESSION_COOKIE_ENV_VAR}"),
+            Source::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// A session cookie used to authenticate with Advent of Code, and where it
+/// came from so it can be replaced when it expires.
+#[derive(Debug, PartialEq)]
+pub struct Session {
+    pub cookie: String,
+    pub source: Source,
+}
+
+/// Where the session cookie is saved, following the XDG base directories.
+pub fn session_file() -> Option<PathBuf> {
+    let config = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))?;
+    Some(config.join("aoc").join("session"))
+}
+
+/// Refuse a cookie file others can read, as the cookie is as good as a
+/// password.
+#[cfg(unix)]
+fn check_permissions(path: &Path) -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+    let metadata =
+        fs::metadata(path).map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+    let mode = metadata.permissions().mode();
+    if mode & 0o077 != 0 {
+        return Err(format!(
+            "{} can be read by other users (mode {:o}), run `chmod 600 {0}`",
+            path.display(),
+            mode & 0o777
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn check_permissions(_path: &Path) -> Result<(), String> {
+    Ok(())
+}
+
+/// The cookie in `value`, which may be copied along with its name.
+fn parse(value: &str, source: &Source) -> Result<String, String> {
+    let cookie = value.trim();
+    let cookie = cookie.strip_prefix("session=").unwrap_or(cookie);
+    if cookie.is_empty() || !cookie.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!(
+            "the session cookie from {source} should be hexadecimal digits"
+        ));
+    }
+    Ok(cookie.to_string())
+}
+
+/// The session cookie from `env` if given, otherwise from `file`.
+fn load_from(env: Option<String>, file: Option<PathBuf>) -> Result<Session, String> {
+    if let Some(value) = env {
+        let source = Source::Env;
+        let cookie = parse(&value, &source)?;
+        return Ok(Session { cookie, source });
+    }
+    let missing = || match &file {
+        Some(path) => format!(
+            "no session cookie, set ${SESSION_COOKIE_ENV_VAR} or save it to {}",
+            path.display()
+        ),
+        None => format!("no session cookie, set ${SESSION_COOKIE_ENV_VAR}"),
+    };
+    let path = file
+        .clone()
+        .filter(|path| path.exists())
+        .ok_or_else(missing)?;
+    check_permissions(&path)?;
+    let value =
+        fs::read_to_string(&path).map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+    let source = Source::File(path);
+    let cookie = parse(&value, &source)?;
+    Ok(Session { cookie, source })
+}
+
+/// The session cookie from the environment or the session file.
+pub fn load() -> Result<Session, String> {
+    load_from(env::var(SESSION_COOKIE_ENV_VAR).ok(), session_file())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn sources() {
+        let dir = env::temp_dir().join(format!("fetch-credentials-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("session");
+
+        let session = load_from(Some(" session=53616c74\n".to_string()), Some(file.clone()));
+        assert_eq!(
+            session,
+            Ok(Session {
+                cookie: "53616c74".to_string(),
+                source: Source::Env
+            })
+        );
+        assert_eq!(
+            load_from(None, Some(file.clone())),
+            Err(format!(
+                "no session cookie, set $AOC_SESSION_COOKIE or save it to {}",
+                file.display()
+            ))
+        );
+
+        fs::write(&file, "53616c74\n").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&file, fs::Permissions::from_mode(0o644)).unwrap();
+            assert!(load_from(None, Some(file.clone()))
+                .unwrap_err()
+                .contains("can be read by other users (mode 644)"));
+            fs::set_permissions(&file, fs::Permissions::from_mode(0o600)).unwrap();
+        }
+        assert_eq!(
+            load_from(None, Some(file.clone())),
+            Ok(Session {
+                cookie: "53616c74".to_string(),
+                source: Source::File(file.clone())
+            })
+        );
+
+        fs::write(&file, "not a cookie").unwrap();
+        assert_eq!(
+            load_from(None, Some(file.clone())),
+            Err(format!(
+                "the session cookie from {} should be hexadecimal digits",
+                file.display()
+            ))
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,146 @@
+//! Pulls the examples out of a puzzle's description, along with the answers
+//! the description gives for them, so solutions can be tested against them.
+//!
+//! Examples are the `<pre><code>` blocks, and an example's answer is the last
+//! highlighted `<code><em>` before the next example. A part without examples
+//! of its own answers for the last example before it.
+
+use crate::html::{self, Node};
+use serde::Serialize;
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Example {
+    pub input: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part2: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct Examples<'a> {
+    example: &'a [Example],
+}
+
+#[derive(Debug, PartialEq)]
+enum Found {
+    Example(String),
+    Answer(String),
+}
+
+/// Examples and answers within `node`, in the order they appear.
+fn find(node: &Node, found: &mut Vec<Found>) {
+    match node.name() {
+        Some("pre") => {
+            let input = node.text();
+            found.push(Found::Example(
+                input.trim_end_matches('\n').to_string() + "\n",
+            ));
+        }
+        Some("code") if is_highlighted(node) => found.push(Found::Answer(node.text())),
+        _ => node.children().iter().for_each(|child| find(child, found)),
+    }
+}
+
+/// Whether all of a code element is emphasized, as answers are.
+fn is_highlighted(code: &Node) -> bool {
+    match code.children() {
+        [em] => em.name() == Some("em") && !em.text().trim().is_empty(),
+        _ => false,
+    }
+}
+
+/// The examples in the puzzle page `html` with the answers given for them.
+pub fn extract(html: &str) -> Vec<Example> {
+    let document = html::parse(html);
+    let parts = html::puzzle(&document)
+        .into_iter()
+        .filter(|node| node.name() == Some("article"));
+    let mut examples: Vec<Example> = vec![];
+    for (idx, part) in parts.enumerate().take(2) {
+        let mut found = vec![];
+        find(part, &mut found);
+        // the example each answer is for, starting with the last one before
+        // this part in case it has none of its own
+        let mut current = examples.len().checked_sub(1);
+        for item in found {
+            match item {
+                Found::Example(input) => {
+                    current = match examples.iter().position(|e| e.input == input) {
+                        Some(existing) => Some(existing),
+                        None => {
+                            examples.push(Example {
+                                input,
+                                part1: None,
+                                part2: None,
+                            });
+                            Some(examples.len() - 1)
+                        }
+                    };
+                }
+                Found::Answer(answer) => {
+                    if let Some(example) = current.map(|current| &mut examples[current]) {
+                        let part = if idx == 0 {
+                            &mut example.part1
+                        } else {
+                            &mut example.part2
+                        };
+                        *part = Some(answer.trim().to_string());
+                    }
+                }
+            }
+        }
+    }
+    examples.retain(|example| example.part1.is_some() || example.part2.is_some());
+    examples
+}
+
+/// The examples as the TOML the solutions' tests read.
+pub fn to_toml(examples: &[Example]) -> Result<String, String> {
+    let toml = toml::to_string(&Examples { example: examples }).map_err(|e| e.to_string())?;
+    Ok(format!(
+        "# Examples from the puzzle description, checked by `cargo test`.\n\n{toml}"
+    ))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn day02() {
+        let examples = extract(include_str!("../fixtures/day02.html"));
+        insta::assert_snapshot!(to_toml(&examples).unwrap());
+    }
+
+    #[test]
+    fn day03() {
+        let examples = extract(include_str!("../fixtures/day03.html"));
+        insta::assert_snapshot!(to_toml(&examples).unwrap());
+    }
+
+    #[test]
+    fn answers() {
+        let html = r#"<main><article class="day-desc"><p>A diagram:</p><pre><code>.#.</code></pre>
+            <p>For example:</p><pre><code>1 2</code></pre><p>Gives <code>1 2</code> <code><em>3</em></code>,
+            not <code><em>4</em> or so</code>.</p></article>
+            <article class="day-desc"><p>Now it gives <code><em>7</em></code>.</p>
+            <pre><code>5 6</code></pre><p>And then <code><em>11</em></code>.</p></article></main>"#;
+        assert_eq!(
+            extract(html),
+            vec![
+                Example {
+                    input: "1 2\n".to_string(),
+                    part1: Some("3".to_string()),
+                    part2: Some("7".to_string()),
+                },
+                Example {
+                    input: "5 6\n".to_string(),
+                    part1: None,
+                    part2: Some("11".to_string()),
+                },
+            ]
+        );
+        assert_eq!(extract("<p>Not a puzzle</p>"), vec![]);
+    }
+}
@@ -0,0 +1,297 @@
+//! Parses Advent of Code's pages. The parser is only as lenient as those
+//! pages need: tags are closed by the nearest matching open tag, and the
+//! contents of scripts and styles are skipped.
+
+/// Elements that never have children.
+const VOID: &[&str] = &["br", "hr", "img", "input", "link", "meta"];
+/// Elements whose contents aren't HTML.
+const RAW_TEXT: &[&str] = &["script", "style"];
+
+#[derive(Debug, PartialEq)]
+pub enum Node {
+    Element {
+        name: String,
+        attrs: Vec<(String, String)>,
+        children: Vec<Node>,
+    },
+    Text(String),
+}
+
+impl Node {
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Node::Element { name, .. } => Some(name),
+            Node::Text(_) => None,
+        }
+    }
+
+    pub fn attr(&self, key: &str) -> Option<&str> {
+        match self {
+            Node::Element { attrs, .. } => attrs
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value.as_str()),
+            Node::Text(_) => None,
+        }
+    }
+
+    pub fn children(&self) -> &[Node] {
+        match self {
+            Node::Element { children, .. } => children,
+            Node::Text(_) => &[],
+        }
+    }
+
+    /// All text within the node, as is.
+    pub fn text(&self) -> String {
+        match self {
+            Node::Element { children, .. } => children.iter().map(Node::text).collect(),
+            Node::Text(text) => text.clone(),
+        }
+    }
+
+    /// The first element named `name`, searching depth first.
+    pub fn find(&self, name: &str) -> Option<&Node> {
+        self.find_where(&|node| node.name() == Some(name))
+    }
+
+    /// The first node `matches`, searching depth first.
+    pub fn find_where(&self, matches: &dyn Fn(&Node) -> bool) -> Option<&Node> {
+        if matches(self) {
+            return Some(self);
+        }
+        self.children()
+            .iter()
+            .find_map(|child| child.find_where(matches))
+    }
+
+    /// Every node that `matches`, depth first.
+    pub fn find_all(&self, matches: &dyn Fn(&Node) -> bool) -> Vec<&Node> {
+        let mut found: Vec<&Node> = self
+            .children()
+            .iter()
+            .flat_map(|child| child.find_all(matches))
+            .collect();
+        if matches(self) {
+            found.insert(0, self);
+        }
+        found
+    }
+}
+
+/// Replace character references with the characters they stand for.
+fn decode(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest[1..]
+            .find(';')
+            .filter(|end| *end <= 10)
+            .map(|end| &rest[1..end + 1]);
+        let character = entity.and_then(|entity| match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some('\u{a0}'),
+            _ => {
+                let code = match entity.strip_prefix("#x").or(entity.strip_prefix("#X")) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                    None => entity.strip_prefix('#')?.parse().ok(),
+                };
+                code.and_then(char::from_u32)
+            }
+        });
+        match (entity, character) {
+            (Some(entity), Some(character)) => {
+                decoded.push(character);
+                rest = &rest[entity.len() + 2..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// Parse a start tag's name and attributes from just after its `<`,
+/// returning them with whether the tag closed itself and the rest of the
+/// input after its `>`.
+fn start_tag(html: &str) -> (String, Vec<(String, String)>, bool, &str) {
+    let is_name_end = |c: char| c.is_whitespace() || c == '>' || c == '/';
+    let end = html.find(is_name_end).unwrap_or(html.len());
+    let name = html[..end].to_ascii_lowercase();
+    let mut rest = &html[end..];
+    let mut attrs = vec![];
+    loop {
+        rest = rest.trim_start();
+        if let Some(after) = rest.strip_prefix("/>") {
+            return (name, attrs, true, after);
+        }
+        if let Some(after) = rest.strip_prefix('>') {
+            return (name, attrs, false, after);
+        }
+        if rest.is_empty() {
+            return (name, attrs, false, rest);
+        }
+        let end = rest
+            .find(|c: char| c.is_whitespace() || c == '=' || c == '>' || c == '/')
+            .unwrap_or(rest.len())
+            .max(1);
+        let key = rest[..end].to_ascii_lowercase();
+        rest = rest[end..].trim_start();
+        let value = match rest.strip_prefix('=') {
+            Some(after) => {
+                let after = after.trim_start();
+                let (value, remaining) = match after.chars().next() {
+                    Some(quote @ ('"' | '\'')) => {
+                        let end = after[1..].find(quote).map_or(after.len(), |end| end + 1);
+                        (&after[1..end], after.get(end + 1..).unwrap_or(""))
+                    }
+                    _ => {
+                        let end = after
+                            .find(|c: char| c.is_whitespace() || c == '>')
+                            .unwrap_or(after.len());
+                        after.split_at(end)
+                    }
+                };
+                rest = remaining;
+                decode(value)
+            }
+            None => String::new(),
+        };
+        attrs.push((key, value));
+    }
+}
+
+/// An element whose end tag hasn't been seen yet: its name, attributes and
+/// children so far.
+type Open = (String, Vec<(String, String)>, Vec<Node>);
+
+fn nodes(html: &str) -> Vec<Node> {
+    // open elements, with the document itself at the bottom
+    let mut stack: Vec<Open> = vec![(String::new(), vec![], vec![])];
+    let close = |stack: &mut Vec<Open>| {
+        let (name, attrs, children) = stack.pop().unwrap();
+        let element = Node::Element {
+            name,
+            attrs,
+            children,
+        };
+        stack.last_mut().unwrap().2.push(element);
+    };
+
+    let mut rest = html;
+    while !rest.is_empty() {
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+        } else if let Some(declaration) = rest.strip_prefix("<!") {
+            rest = declaration
+                .find('>')
+                .map_or("", |end| &declaration[end + 1..]);
+        } else if let Some(tag) = rest.strip_prefix("</") {
+            let end = tag.find('>').unwrap_or(tag.len());
+            let name = tag[..end].trim().to_ascii_lowercase();
+            rest = tag.get(end + 1..).unwrap_or("");
+            if let Some(open) = stack.iter().rposition(|(open, _, _)| *open == name) {
+                while stack.len() > open.max(1) {
+                    close(&mut stack);
+                }
+            }
+        } else if rest.starts_with('<') && rest[1..].starts_with(|c: char| c.is_alphabetic()) {
+            let (name, attrs, closed, after) = start_tag(&rest[1..]);
+            rest = after;
+            if RAW_TEXT.contains(&name.as_str()) {
+                let end = rest
+                    .to_ascii_lowercase()
+                    .find(&format!("</{name}"))
+                    .unwrap_or(rest.len());
+                rest = &rest[end..];
+            }
+            stack.push((name.clone(), attrs, vec![]));
+            if closed || VOID.contains(&name.as_str()) || RAW_TEXT.contains(&name.as_str()) {
+                close(&mut stack);
+            }
+        } else {
+            let first = rest.chars().next().map_or(0, char::len_utf8);
+            let end = rest[first..]
+                .find('<')
+                .map_or(rest.len(), |end| end + first);
+            let text = decode(&rest[..end]);
+            stack.last_mut().unwrap().2.push(Node::Text(text));
+            rest = &rest[end..];
+        }
+    }
+    while stack.len() > 1 {
+        close(&mut stack);
+    }
+    stack.pop().unwrap().2
+}
+
+/// Parse a whole page, as an element with no name holding its top level
+/// nodes.
+pub fn parse(html: &str) -> Node {
+    Node::Element {
+        name: String::new(),
+        attrs: vec![],
+        children: nodes(html),
+    }
+}
+
+/// The description of each part of the puzzle on the page, along with the
+/// paragraphs after them giving the answers so far.
+pub fn puzzle(document: &Node) -> Vec<&Node> {
+    let main = document.find("main").unwrap_or(document);
+    main.children()
+        .iter()
+        .filter(|node| match node.name() {
+            Some("article") => node.attr("class") == Some("day-desc"),
+            Some("p") => node.text().starts_with("Your puzzle answer was"),
+            _ => false,
+        })
+        .collect()
+}
+
+/// The name of the user the page was shown to, if they were logged in.
+pub fn user(document: &Node) -> Option<String> {
+    let user = document.find_where(&|node| node.attr("class") == Some("user"))?;
+    let name: String = user
+        .children()
+        .iter()
+        .filter_map(|child| match child {
+            Node::Text(text) => Some(text.as_str()),
+            Node::Element { .. } => None,
+        })
+        .collect();
+    let name = name.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing() {
+        let html =
+            r#"<p class="a&quot;b">1 &lt; 2 &amp;&nbsp;<br>x<!-- gone --><script>y</script></p>"#;
+        let document = parse(html);
+        let p = document.find("p").unwrap();
+        assert_eq!(p.attr("class"), Some("a\"b"));
+        assert_eq!(p.text(), "1 < 2 &\u{a0}x");
+        assert!(p.find("br").is_some_and(|br| br.children().is_empty()));
+        assert!(p
+            .find("script")
+            .is_some_and(|script| script.children().is_empty()));
+        assert_eq!(
+            user(&parse(r#"<div class="user">someone <span>5*</span></div>"#)).as_deref(),
+            Some("someone")
+        );
+    }
+}
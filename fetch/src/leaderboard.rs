@@ -0,0 +1,374 @@
+//! A private leaderboard: its standings, when each star was got and the local
+//! score each earned. Advent of Code asks for it at most every 15 minutes, so
+//! it's cached for that long.
+
+use crate::{unlock, validate, Config};
+use serde::Deserialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    env, fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+pub const CACHE_FOR: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug, Deserialize)]
+pub struct Leaderboard {
+    pub members: HashMap<String, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    /// When each part of each day was solved.
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<u8, BTreeMap<u8, Star>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Star {
+    pub get_star_ts: u64,
+}
+
+impl Member {
+    /// The member's name, or what Advent of Code calls them without one.
+    pub fn name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    pub fn star(&self, day: u8, part: u8) -> Option<SystemTime> {
+        let star = self.completion_day_level.get(&day)?.get(&part)?;
+        Some(UNIX_EPOCH + Duration::from_secs(star.get_star_ts))
+    }
+}
+
+impl Leaderboard {
+    /// Members from first to last place.
+    pub fn members(&self) -> Vec<&Member> {
+        let mut members: Vec<&Member> = self.members.values().collect();
+        members.sort_by_key(|member| {
+            (
+                std::cmp::Reverse(member.local_score),
+                std::cmp::Reverse(member.stars),
+                member.id,
+            )
+        });
+        members
+    }
+
+    /// The last day anyone has a star for.
+    pub fn last_day(&self) -> Option<u8> {
+        self.members
+            .values()
+            .filter_map(|member| member.completion_day_level.keys().max())
+            .max()
+            .copied()
+    }
+
+    /// The local score each member earned for each part of each day, keyed
+    /// by member, day and part: as many points as there are members for the
+    /// first to get the star, one fewer for the second, and so on.
+    pub fn points(&self) -> HashMap<(u64, u8, u8), u32> {
+        let count = self.members.len() as u32;
+        let mut points = HashMap::new();
+        for day in 1..=25 {
+            for part in 1..=2 {
+                let mut solved: Vec<(SystemTime, u64)> = self
+                    .members
+                    .values()
+                    .filter_map(|member| Some((member.star(day, part)?, member.id)))
+                    .collect();
+                solved.sort();
+                for (place, (_, id)) in solved.into_iter().enumerate() {
+                    points.insert((id, day, part), count - place as u32);
+                }
+            }
+        }
+        points
+    }
+}
+
+/// Where leaderboard `id` of `year` is cached, following the XDG base
+/// directories.
+pub fn cache_file(year: u16, id: u64) -> Option<PathBuf> {
+    let cache = env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .or_else(|| env::var_os("LOCALAPPDATA").map(PathBuf::from))?;
+    Some(
+        cache
+            .join("aoc")
+            .join(format!("leaderboard-{year}-{id}.json")),
+    )
+}
+
+fn parse(json: &str) -> Result<Leaderboard, String> {
+    serde_json::from_str(json).map_err(|e| format!("leaderboard isn't what was expected: {e}"))
+}
+
+/// What's cached at `path` and how old it is, if it's still fresh at `now`.
+fn cached(path: &Path, now: SystemTime) -> Option<(String, Duration)> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    let age = now.duration_since(modified).unwrap_or_default();
+    if age >= CACHE_FOR {
+        return None;
+    }
+    Some((fs::read_to_string(path).ok()?, age))
+}
+
+/// Leaderboard `id`, from `cache` if it's fresh at `now` or otherwise fetched
+/// and cached there, along with how old it is.
+pub fn load(
+    config: &Config,
+    id: u64,
+    cache: &Path,
+    now: SystemTime,
+) -> Result<(Leaderboard, Duration), String> {
+    if let Some((json, age)) = cached(cache, now) {
+        return Ok((parse(&json)?, age));
+    }
+    let response = config.get(&config.leaderboard_url(id))?;
+    validate::leaderboard(&response)?;
+    let leaderboard = parse(&response.body)?;
+    crate::write(cache, &response.body)?;
+    Ok((leaderboard, Duration::ZERO))
+}
+
+/// The days as a row of `*` for both stars, `+` for only the first and `.`
+/// for neither.
+fn stars(member: &Member, days: u8) -> String {
+    (1..=days)
+        .map(|day| match (member.star(day, 1), member.star(day, 2)) {
+            (Some(_), Some(_)) => '*',
+            (Some(_), None) => '+',
+            _ => '.',
+        })
+        .collect()
+}
+
+/// A row per member in order of local score, with their stars.
+pub fn standings(leaderboard: &Leaderboard) -> Vec<String> {
+    let days = leaderboard.last_day().unwrap_or(0);
+    let width = usize::from(days).max(4);
+    let mut rows = vec![format!("rank score stars  {:<width$}  name", "days")];
+    for (place, member) in leaderboard.members().into_iter().enumerate() {
+        rows.push(format!(
+            "{:>4} {:>5} {:>5}  {:<width$}  {}",
+            place + 1,
+            member.local_score,
+            member.stars,
+            stars(member, days),
+            member.name()
+        ));
+    }
+    rows
+}
+
+/// A row per member with the local score they earned each day, up to the
+/// last day anyone has a star for.
+pub fn scores(leaderboard: &Leaderboard) -> Vec<String> {
+    let days = 1..=leaderboard.last_day().unwrap_or(0);
+    let points = leaderboard.points();
+    let members = leaderboard.members();
+    let width = members
+        .iter()
+        .map(|member| member.name().len())
+        .chain(["name".len()])
+        .max()
+        .unwrap_or_default();
+
+    let header: String = days.clone().map(|day| format!("{day:>4}")).collect();
+    let mut rows = vec![format!("{:<width$}{header}  total", "name")];
+    for member in members {
+        let earned: Vec<u32> = days
+            .clone()
+            .map(|day| {
+                (1..=2)
+                    .filter_map(|part| points.get(&(member.id, day, part)))
+                    .sum()
+            })
+            .collect();
+        let row: String = earned.iter().map(|points| format!("{points:>4}")).collect();
+        let total: u32 = earned.iter().sum();
+        rows.push(format!("{:<width$}{row}  {total:>5}", member.name()));
+    }
+    rows
+}
+
+/// A row per member with a star on `day` of `year`: how long after the
+/// puzzle unlocked they got each, how long part 2 took after part 1, and the
+/// points each earned. Fastest to both stars first.
+pub fn day(leaderboard: &Leaderboard, year: u16, day: u8) -> Result<Vec<String>, String> {
+    let unlock = unlock::unlock_time(year, day)?;
+    let after =
+        |time: SystemTime| unlock::countdown(time.duration_since(unlock).unwrap_or_default());
+    let points = leaderboard.points();
+
+    let mut solvers: Vec<&Member> = leaderboard
+        .members
+        .values()
+        .filter(|member| member.star(day, 1).is_some())
+        .collect();
+    solvers.sort_by_key(|member| {
+        (
+            member.star(day, 2).is_none(),
+            member.star(day, 2),
+            member.star(day, 1),
+            member.id,
+        )
+    });
+    let width = solvers
+        .iter()
+        .map(|member| member.name().len())
+        .chain([format!("day {day}").len()])
+        .max()
+        .unwrap_or_default();
+
+    let row = |name: &str, part1: &str, part2: &str, delta: &str, points: &str| {
+        format!("{name:<width$}  {part1:>11}  {part2:>11}  {delta:>11}  {points}")
+            .trim_end()
+            .to_string()
+    };
+    let mut rows = vec![row(
+        &format!("day {day}"),
+        "part 1",
+        "part 2",
+        "delta",
+        "points",
+    )];
+    for member in solvers {
+        let (part1, part2) = (member.star(day, 1), member.star(day, 2));
+        let earned: Vec<String> = (1..=2)
+            .filter_map(|part| points.get(&(member.id, day, part)))
+            .map(u32::to_string)
+            .collect();
+        let delta = match (part1, part2) {
+            (Some(part1), Some(part2)) => {
+                unlock::countdown(part2.duration_since(part1).unwrap_or_default())
+            }
+            _ => String::new(),
+        };
+        rows.push(row(
+            &member.name(),
+            &part1.map(after).unwrap_or_default(),
+            &part2.map(after).unwrap_or_default(),
+            &delta,
+            &earned.join(" + "),
+        ));
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        credentials::{Session, Source},
+        settings::Settings,
+        stand_in::StandIn,
+        unlock::RateLimit,
+    };
+    use std::cell::RefCell;
+
+    const FIXTURE: &str = include_str!("../fixtures/leaderboard.json");
+
+    #[test]
+    fn local_scores() {
+        let leaderboard = parse(FIXTURE).unwrap();
+        let points = leaderboard.points();
+        for member in leaderboard.members.values() {
+            let earned: u32 = points
+                .iter()
+                .filter(|((id, _, _), _)| *id == member.id)
+                .map(|(_, points)| points)
+                .sum();
+            assert_eq!(earned, member.local_score, "{}", member.name());
+        }
+    }
+
+    #[test]
+    fn rendering() {
+        let leaderboard = parse(FIXTURE).unwrap();
+        insta::assert_snapshot!(standings(&leaderboard).join("\n"), @r"
+        rank score stars  days  name
+           1    18     5  **+   Ada Lovelace
+           2    15     6  ***   (anonymous user #303)
+           3    14     4  **.   grace
+           4     0     0  ...   Edsger
+        ");
+        insta::assert_snapshot!(scores(&leaderboard).join("\n"), @r"
+        name                    1   2   3  total
+        Ada Lovelace            7   7   4     18
+        (anonymous user #303)   4   4   7     15
+        grace                   7   7   0     14
+        Edsger                  0   0   0      0
+        ");
+        insta::assert_snapshot!(day(&leaderboard, 2024, 2).unwrap().join("\n"), @r"
+        day 2                       part 1       part 2        delta  points
+        grace                     00:20:00     00:22:35     00:02:35  3 + 4
+        Ada Lovelace              00:15:05     00:27:10     00:12:05  4 + 3
+        (anonymous user #303)  1d 00:00:20  1d 00:11:40     00:11:20  2 + 2
+        ");
+    }
+
+    #[test]
+    fn caching() {
+        let stand_in = StandIn::serve(vec![
+            (
+                "/2024/leaderboard/private/view/101.json",
+                "application/json",
+                FIXTURE.to_string(),
+            ),
+            (
+                "/2024/leaderboard/private/view/202.json",
+                "text/html",
+                "<html><main>Leaderboard</main></html>".to_string(),
+            ),
+        ]);
+        let config = Config {
+            settings: Settings {
+                base_url: stand_in.url.clone(),
+                ..Settings::default()
+            },
+            session: Session {
+                cookie: "53616c74".to_string(),
+                source: Source::Env,
+            },
+            limit: RefCell::new(RateLimit::new(Duration::ZERO)),
+        };
+        let dir = env::temp_dir().join(format!("fetch-leaderboard-{}", std::process::id()));
+        let cache = dir.join("leaderboard.json");
+
+        let now = SystemTime::now();
+        let (leaderboard, age) = load(&config, 101, &cache, now).unwrap();
+        assert_eq!((leaderboard.members.len(), age), (4, Duration::ZERO));
+        let (_, age) = load(&config, 101, &cache, now + Duration::from_secs(60)).unwrap();
+        assert!(age > Duration::ZERO);
+        assert_eq!(stand_in.requests().len(), 1);
+        load(
+            &config,
+            101,
+            &cache,
+            now + CACHE_FOR + Duration::from_secs(60),
+        )
+        .unwrap();
+        assert_eq!(stand_in.requests().len(), 2);
+
+        let other = dir.join("other.json");
+        assert_eq!(
+            load(&config, 202, &other, now).unwrap_err(),
+            "leaderboard is text/html rather than JSON, \
+             check its id and that you're a member of it"
+        );
+        assert!(!other.exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -6,49 +6,190 @@
 //! utilities are not installed. You probably don't need to run this, it's just
 //! for my convenience.
 
-use duct::cmd;
-use std::{env, fs};
+use bulk::{Days, Status};
+use clap::{Parser, Subcommand, ValueEnum};
+use credentials::Session;
+use settings::Settings;
+use std::{
+    cell::RefCell,
+    env, fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use unlock::{Clock, RateLimit, SystemClock};
+use validate::Response;
 
-pub const SESSION_COOKIE_ENV_VAR: &str = "AOC_SESSION_COOKIE";
+mod bulk;
+mod credentials;
+mod examples;
+mod html;
+mod leaderboard;
+mod markdown;
+mod progress;
+mod settings;
+#[cfg(test)]
+mod stand_in;
+mod text;
+mod unlock;
+mod validate;
+
+/// Fetch a puzzle's description and input, by default into
+/// `solutions/src/bin` (see `aoc.toml`).
+#[derive(Debug, Parser)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+struct Options {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Day of the puzzle, or a range of days such as `1..=10`, fetching only
+    /// those that unlocked and haven't been fetched.
+    #[arg(required_unless_present = "all")]
+    days: Option<Days>,
+    /// Fetch every day that unlocked and hasn't been fetched.
+    #[arg(long, conflicts_with = "days")]
+    all: bool,
+    /// Year of the puzzle, by default 2024 or the one in `aoc.toml`.
+    #[arg(long, global = true)]
+    year: Option<u16>,
+    /// Site to fetch from.
+    #[arg(long, global = true)]
+    base_url: Option<String>,
+    /// Commands to convert the description to Markdown with, separated by
+    /// `|`, by default `rdrview -H | pandoc -f html -t gfm`.
+    #[arg(long, global = true)]
+    converter: Option<String>,
+    /// If the puzzle hasn't unlocked yet, wait until it has.
+    #[arg(long)]
+    wait: bool,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Check the session cookie works, printing whose it is.
+    Whoami,
+    /// Save the stars earned each day, from the calendar, for the README's
+    /// progress table.
+    Progress,
+    /// Show a private leaderboard, fetched at most every 15 minutes.
+    Leaderboard {
+        /// Id of the leaderboard, the number at the end of its URL.
+        id: u64,
+        /// Day to show when each star was got, by default the latest.
+        #[arg(long)]
+        day: Option<u8>,
+    },
+    /// Convert saved descriptions again, without fetching anything.
+    Render {
+        /// Day of the puzzle, or a range of days such as `1..=10`.
+        #[arg(required_unless_present = "all")]
+        days: Option<Days>,
+        /// Every day whose description has been saved.
+        #[arg(long, conflicts_with = "days")]
+        all: bool,
+        /// What to convert them to.
+        #[arg(long, value_enum, default_value_t = Format::Markdown)]
+        format: Format,
+        /// Columns to wrap text to.
+        #[arg(long, default_value_t = text::WIDTH)]
+        width: usize,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum Format {
+    /// Markdown saved over the description, as when fetching.
+    Markdown,
+    /// Plain text, printed.
+    Text,
+    /// Text styled for a terminal, printed.
+    Terminal,
+}
 
 /// Required application runtime configuration.
 #[derive(Debug)]
 pub struct Config {
-    /// The path of the current problem, e.g. "2024/day/1".
-    pub day: i64,
-    /// A session cookie used to authenticate with Advent of Code.
-    pub session_cookie: String,
+    pub settings: Settings,
+    pub session: Session,
+    /// Spaces out requests, however many days are fetched.
+    limit: RefCell<RateLimit>,
+}
+
+/// Settings from `aoc.toml` and the environment, overridden by `options`.
+fn settings(options: &Options) -> Result<Settings, String> {
+    let cwd = env::current_dir().map_err(|e| e.to_string())?;
+    let mut settings = Settings::load(&cwd)?;
+    if let Some(year) = options.year {
+        settings.year = year;
+    }
+    if let Some(base_url) = &options.base_url {
+        settings.base_url = base_url.clone();
+    }
+    if let Some(converter) = &options.converter {
+        settings.converter = converter.clone();
+    }
+    Ok(settings)
 }
 
 impl Config {
-    const BASE_URL: &str = "https://adventofcode.com/2024/day/";
-
-    pub fn parse() -> Result<Self, String> {
-        // parse cli args
-        let day = match &env::args().collect::<Vec<String>>()[..] {
-            [_, day] => day
-                .parse::<i64>()
-                .map_err(|e| format!("failed to convert date: {}", e))?,
-            [cmd, ..] => Err(format!("usage: {cmd} <day>"))?,
-            _ => Err("usage: fetch <day>")?,
-        };
-        // parse SESSION_COOKIE
-        let session_cookie = match env::var(SESSION_COOKIE_ENV_VAR) {
-            Ok(secret) => secret,
-            Err(e) => Err(format!("please set {SESSION_COOKIE_ENV_VAR}: {e}"))?,
-        };
+    fn new(options: &Options) -> Result<Self, String> {
+        let settings = settings(options)?;
+        let limit = RateLimit::new(Duration::from_secs(settings.request_interval));
         Ok(Config {
-            day,
-            session_cookie,
+            settings,
+            session: credentials::load()?,
+            limit: RefCell::new(limit),
         })
     }
 
-    fn description_url(&self) -> String {
-        String::from(Self::BASE_URL) + format!("{:01}", &self.day).as_str()
+    fn description_url(&self, day: u8) -> String {
+        self.settings.description_url(day)
+    }
+
+    fn input_url(&self, day: u8) -> String {
+        self.description_url(day) + "/input"
+    }
+
+    fn settings_url(&self) -> String {
+        let Settings { base_url, year, .. } = &self.settings;
+        format!("{}/{year}/settings", base_url.trim_end_matches('/'))
     }
 
-    fn input_url(&self) -> String {
-        self.description_url() + "/input"
+    fn calendar_url(&self) -> String {
+        let Settings { base_url, year, .. } = &self.settings;
+        format!("{}/{year}", base_url.trim_end_matches('/'))
+    }
+
+    fn leaderboard_url(&self, id: u64) -> String {
+        let Settings { base_url, year, .. } = &self.settings;
+        format!(
+            "{}/{year}/leaderboard/private/view/{id}.json",
+            base_url.trim_end_matches('/')
+        )
+    }
+
+    /// Get `url`, whatever the status of the response, unless the session
+    /// cookie wasn't accepted.
+    fn get(&self, url: &str) -> Result<Response, String> {
+        self.limit.borrow_mut().wait(&mut SystemClock);
+        let response = match ureq::get(url)
+            .set("Cookie", &format!("session={}", self.session.cookie))
+            .set("User-Agent", &self.settings.user_agent)
+            .call()
+        {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+            Err(e) => return Err(e.to_string()),
+        };
+        let response = Response {
+            status: response.status(),
+            content_type: response.content_type().to_string(),
+            body: response.into_string().map_err(|e| e.to_string())?,
+        };
+        if validate::logged_out(&response) {
+            return Err(format!(
+                "not logged in, the session cookie from {} is wrong or has expired",
+                self.session.source
+            ));
+        }
+        Ok(response)
     }
 }
 
@@ -62,61 +203,275 @@ pub struct Problem {
 }
 
 impl Problem {
-    fn request(config: &Config, path: String) -> Result<ureq::Response, Box<ureq::Error>> {
-        Ok(ureq::get(&path)
-            .set(
-                "Cookie",
-                format!("session={}", config.session_cookie).as_str(),
-            )
-            .call()?)
-    }
-
-    pub fn download(config: &Config) -> Result<Self, String> {
-        let description = Self::request(config, config.description_url())
-            .map_err(|e| e.to_string())?
-            .into_string()
-            .map_err(|e| e.to_string())?;
-        let input = Self::request(config, config.input_url())
-            .map_err(|e| e.to_string())?
-            .into_string()
-            .map_err(|e| e.to_string())?;
-        Ok(Problem { description, input })
+    /// Download the description and input, failing unless both are what was
+    /// asked for.
+    pub fn download(config: &Config, day: u8) -> Result<Self, String> {
+        let description = config.get(&config.description_url(day))?;
+        validate::description(&description)?;
+        let input = config.get(&config.input_url(day))?;
+        validate::input(&input)?;
+        Ok(Problem {
+            description: description.body,
+            input: input.body,
+        })
     }
 
-    const BASE_PATH: &str = "solutions/src/bin";
+    fn save(&self, config: &Config, day: u8) -> Result<Success, String> {
+        let settings = &config.settings;
+        let problem_path = settings.description_path(day);
+        let input_path = settings.input_path(day);
+        let examples_path = settings.examples_path(day);
+        let page_path = settings.page_path(day);
 
-    fn save(&self, config: &Config) -> Result<Success, String> {
-        let problem_path = format!("{}/day{:02}.md", Self::BASE_PATH, &config.day);
-        let input_path = format!("{}/day{:02}.input", Self::BASE_PATH, &config.day);
+        let problem_markdown = markdown::to_markdown(&self.description, &settings.converter)?;
+        let examples = examples::extract(&self.description);
 
-        let problem_markdown = cmd!("rdrview", "-H")
-            .stdin_bytes(&*self.description)
-            .pipe(cmd!("pandoc", "-f", "html", "-t", "gfm"))
-            .read()
-            .map_err(|e| e.to_string())?;
-
-        fs::write(&problem_path, problem_markdown).map_err(|e| e.to_string())?;
-        fs::write(&input_path, &self.input).map_err(|e| e.to_string())?;
+        write(&problem_path, &problem_markdown)?;
+        write(&input_path, &self.input)?;
+        write(&page_path, &self.description)?;
+        let examples_path = if examples.is_empty() {
+            None
+        } else {
+            write(&examples_path, &examples::to_toml(&examples)?)?;
+            Some(examples_path)
+        };
 
         Ok(Success {
             problem_path,
             input_path,
+            examples_path,
         })
     }
 }
 
+/// Write `contents` to `path`, creating any directories it's in.
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("failed to create {}: {e}", dir.display()))?;
+    }
+    fs::write(path, contents).map_err(|e| format!("failed to write {}: {e}", path.display()))
+}
+
 #[derive(Debug)]
 struct Success {
-    problem_path: String,
-    input_path: String,
+    problem_path: PathBuf,
+    input_path: PathBuf,
+    /// Where the examples were saved, if the description had any.
+    examples_path: Option<PathBuf>,
+}
+
+/// The name of the account the session cookie belongs to.
+fn whoami(config: &Config) -> Result<String, String> {
+    let response = config.get(&config.settings_url())?;
+    html::user(&html::parse(&response.body))
+        .ok_or_else(|| format!("{} has no account name on it", config.settings_url()))
+}
+
+/// Fetch `day` unless it hasn't unlocked (and `wait` isn't set) or both its
+/// description and input are already saved.
+fn fetch_missing(config: &Config, clock: &mut impl Clock, day: u8, wait: bool) -> Status {
+    let settings = &config.settings;
+    let unlock = match unlock::unlock_time(settings.year, day) {
+        Ok(unlock) => unlock,
+        Err(e) => return Status::Failed(e),
+    };
+    if let Ok(remaining) = unlock.duration_since(clock.now()) {
+        if !wait {
+            return Status::Skipped(format!("unlocks in {}", unlock::countdown(remaining)));
+        }
+        println!("waiting for day {day}");
+        if let Err(e) = unlock::wait(clock, unlock, unlock::random_delay(), io::stdout()) {
+            return Status::Failed(e.to_string());
+        }
+    }
+    if settings.input_path(day).exists() && settings.description_path(day).exists() {
+        return Status::Cached;
+    }
+    match Problem::download(config, day).and_then(|problem| problem.save(config, day)) {
+        Ok(_) => Status::Fetched,
+        Err(e) => Status::Failed(e),
+    }
+}
+
+/// Fetch what's missing of `days`, carrying on past failures, then print
+/// what became of each.
+fn fetch_all(config: &Config, days: impl Iterator<Item = u8>, wait: bool) -> Result<(), String> {
+    let mut clock = SystemClock;
+    let mut statuses = vec![];
+    for day in days {
+        let status = fetch_missing(config, &mut clock, day, wait);
+        println!("day {day}: {status}");
+        statuses.push((day, status));
+    }
+    println!();
+    for row in bulk::summary(&statuses) {
+        println!("{row}");
+    }
+    let failed = statuses
+        .iter()
+        .filter(|(_, status)| matches!(status, Status::Failed(_)))
+        .count();
+    if failed > 0 {
+        return Err(format!("{failed} of {} days failed", statuses.len()));
+    }
+    Ok(())
+}
+
+/// Convert the saved pages of `days` to `format`, saving Markdown over the
+/// descriptions and printing text. Days without a saved page are left out,
+/// unless only one was asked for.
+fn render(settings: &Settings, days: Days, format: Format, width: usize) -> Result<(), String> {
+    let (days, one) = match days {
+        Days::One(day) => (day..=day, true),
+        Days::Range(days) => (days, false),
+    };
+    let mut rendered = 0;
+    for day in days {
+        let page_path = settings.page_path(day);
+        if !page_path.exists() {
+            if one {
+                return Err(format!(
+                    "day {day} has no saved page at {}, fetch it again to save one",
+                    page_path.display()
+                ));
+            }
+            continue;
+        }
+        let page = fs::read_to_string(&page_path)
+            .map_err(|e| format!("failed to read {}: {e}", page_path.display()))?;
+        match format {
+            Format::Markdown => {
+                let path = settings.description_path(day);
+                write(&path, &markdown::to_markdown(&page, &settings.converter)?)?;
+                println!("saved problem to {}", path.display());
+            }
+            Format::Text | Format::Terminal => {
+                if rendered > 0 {
+                    println!();
+                }
+                print!("{}", text::render(&page, width, format == Format::Terminal));
+            }
+        }
+        rendered += 1;
+    }
+    if rendered == 0 {
+        return Err(format!(
+            "no days have a saved page in {}, fetch them again to save them",
+            settings.inputs.display()
+        ));
+    }
+    Ok(())
+}
+
+/// Save the stars earned each day to the progress file, returning how many
+/// there are.
+fn save_progress(config: &Config) -> Result<usize, String> {
+    let response = config.get(&config.calendar_url())?;
+    let days = progress::calendar(&html::parse(&response.body));
+    if days.is_empty() {
+        return Err(format!("{} has no days on it", config.calendar_url()));
+    }
+    let settings = &config.settings;
+    write(
+        &settings.progress,
+        &progress::to_toml(settings.year, &days)?,
+    )?;
+    Ok(days.iter().map(|day| usize::from(day.stars)).sum())
+}
+
+/// Print leaderboard `id`'s standings, the local score earned each day and
+/// when each star of `day` was got.
+fn show_leaderboard(config: &Config, id: u64, day: Option<u8>) -> Result<(), String> {
+    let year = config.settings.year;
+    let cache = leaderboard::cache_file(year, id).ok_or("no cache directory")?;
+    let (board, age) = leaderboard::load(config, id, &cache, SystemClock.now())?;
+    match age.as_secs() / 60 {
+        0 => println!("leaderboard {id} of {year}, as of just now\n"),
+        minutes => println!("leaderboard {id} of {year}, as of {minutes} minutes ago\n"),
+    }
+    for row in leaderboard::standings(&board) {
+        println!("{row}");
+    }
+    let Some(day) = day.or(board.last_day()) else {
+        return Ok(());
+    };
+    println!();
+    for row in leaderboard::scores(&board) {
+        println!("{row}");
+    }
+    println!();
+    for row in leaderboard::day(&board, year, day)? {
+        println!("{row}");
+    }
+    Ok(())
 }
 
 fn main() -> Result<(), String> {
-    let config = Config::parse()?;
-    let problem =
-        Problem::download(&config).map_err(|e| format!("failed to download problem: {}", e))?;
-    let success = problem.save(&config)?;
-    println!("saved problem to {}", success.problem_path);
-    println!("saved input to {}", success.input_path);
+    let options = Options::parse();
+    if let Some(Command::Render {
+        days,
+        all,
+        format,
+        width,
+    }) = &options.command
+    {
+        let days = match (all, days) {
+            (true, _) => Days::all(),
+            (false, Some(days)) => days.clone(),
+            (false, None) => return Err("no day given".to_string()),
+        };
+        return render(&settings(&options)?, days, *format, *width);
+    }
+    let config = Config::new(&options)?;
+    match options.command {
+        Some(Command::Whoami) => {
+            let user = whoami(&config)?;
+            println!(
+                "logged in as {user} with the session cookie from {}",
+                config.session.source
+            );
+            return Ok(());
+        }
+        Some(Command::Progress) => {
+            let stars = save_progress(&config)?;
+            println!(
+                "saved {stars} stars to {}, run `aoc readme` to update the README",
+                config.settings.progress.display()
+            );
+            return Ok(());
+        }
+        Some(Command::Leaderboard { id, day }) => return show_leaderboard(&config, id, day),
+        Some(Command::Render { .. }) | None => {}
+    }
+    let days = match (options.all, options.days) {
+        (true, _) => Days::all(),
+        (false, Some(days)) => days,
+        (false, None) => return Err("no day given".to_string()),
+    };
+    let day = match days {
+        Days::One(day) => day,
+        Days::Range(days) => return fetch_all(&config, days, options.wait),
+    };
+
+    let mut clock = SystemClock;
+    let unlock = unlock::unlock_time(config.settings.year, day)?;
+    if options.wait {
+        unlock::wait(&mut clock, unlock, unlock::random_delay(), io::stdout())
+            .map_err(|e| e.to_string())?;
+    } else if clock.now() < unlock {
+        return Err(format!(
+            "day {day} of {} hasn't unlocked yet, use --wait to wait for it",
+            config.settings.year
+        ));
+    }
+    let problem = Problem::download(&config, day)
+        .map_err(|e| format!("failed to download problem: {}", e))?;
+    let success = problem.save(&config, day)?;
+    println!("saved problem to {}", success.problem_path.display());
+    println!("saved input to {}", success.input_path.display());
+    match success.examples_path {
+        Some(path) => println!("saved examples to {}", path.display()),
+        None => println!("found no examples"),
+    }
     Ok(())
 }
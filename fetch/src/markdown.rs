@@ -0,0 +1,63 @@
+//! Converts a puzzle page to Markdown by piping it through the converter's
+//! commands, by default `rdrview` to pick out the puzzle from the rest of the
+//! page and then `pandoc`.
+
+use duct::{cmd, Expression};
+
+/// The commands in `converter`, separated by `|`, with their arguments
+/// separated by whitespace.
+fn pipeline(converter: &str) -> Result<Expression, String> {
+    converter
+        .split('|')
+        .map(|command| {
+            let mut words = command.split_whitespace();
+            let program = words
+                .next()
+                .ok_or_else(|| format!("the converter `{converter}` has an empty command"))?;
+            Ok(cmd(program, words))
+        })
+        .reduce(|piped, command| Ok(piped?.pipe(command?)))
+        .unwrap_or_else(|| Err("the converter has no commands".to_string()))
+}
+
+/// The page `html` as Markdown, by `converter`.
+pub fn to_markdown(html: &str, converter: &str) -> Result<String, String> {
+    pipeline(converter)?
+        .stdin_bytes(html)
+        .read()
+        .map_err(|e| format!("failed to convert with `{converter}` ({e}), is it installed?"))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::settings::Settings;
+
+    #[test]
+    fn converters() {
+        assert_eq!(
+            to_markdown("<p>hi</p>", "tr a-z A-Z | tr P Q").unwrap(),
+            "<Q>HI</Q>"
+        );
+        assert!(to_markdown("", "tr a-z A-Z |")
+            .unwrap_err()
+            .contains("empty command"));
+        assert!(to_markdown("", "no-such-converter")
+            .unwrap_err()
+            .starts_with("failed to convert with `no-such-converter`"));
+    }
+
+    #[test]
+    #[ignore = "needs rdrview and pandoc"]
+    fn day02() {
+        let html = include_str!("../fixtures/day02.html");
+        insta::assert_snapshot!(to_markdown(html, &Settings::default().converter).unwrap());
+    }
+
+    #[test]
+    #[ignore = "needs rdrview and pandoc"]
+    fn day03() {
+        let html = include_str!("../fixtures/day03.html");
+        insta::assert_snapshot!(to_markdown(html, &Settings::default().converter).unwrap());
+    }
+}
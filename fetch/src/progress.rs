@@ -0,0 +1,83 @@
+//! Stars earned each day, read off the calendar and kept in a progress file
+//! so the README's progress table can be made without fetching.
+
+use crate::html::Node;
+use serde::Serialize;
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Stars {
+    pub day: u8,
+    pub stars: u8,
+}
+
+#[derive(Serialize)]
+struct Progress<'a> {
+    year: u16,
+    day: &'a [Stars],
+}
+
+/// The day a calendar entry links to, from its `calendar-dayN` class.
+fn calendar_day(node: &Node) -> Option<u8> {
+    node.attr("class")?
+        .split_whitespace()
+        .find_map(|class| class.strip_prefix("calendar-day")?.parse().ok())
+}
+
+/// Stars for each day that has unlocked, from the calendar page. Locked days
+/// aren't links, so they're left out.
+pub fn calendar(document: &Node) -> Vec<Stars> {
+    let mut days: Vec<Stars> = document
+        .find_all(&|node| node.name() == Some("a") && calendar_day(node).is_some())
+        .into_iter()
+        .filter_map(|link| {
+            let classes = link.attr("class")?;
+            let stars = if classes.contains("calendar-verycomplete") {
+                2
+            } else if classes.contains("calendar-complete") {
+                1
+            } else {
+                0
+            };
+            Some(Stars {
+                day: calendar_day(link)?,
+                stars,
+            })
+        })
+        .collect();
+    days.sort_by_key(|stars| stars.day);
+    days
+}
+
+pub fn to_toml(year: u16, days: &[Stars]) -> Result<String, String> {
+    let toml = toml::to_string(&Progress { year, day: days }).map_err(|e| e.to_string())?;
+    Ok(format!(
+        "# Stars earned each day, saved by `fetch progress` for `aoc readme`.\n\n{toml}"
+    ))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::html;
+
+    #[test]
+    fn calendar_stars() {
+        let document = html::parse(include_str!("../fixtures/calendar.html"));
+        let days = calendar(&document);
+        let stars: Vec<(u8, u8)> = days.iter().map(|day| (day.day, day.stars)).collect();
+        assert_eq!(stars, [(1, 2), (2, 2), (3, 2), (4, 1), (5, 0)]);
+        insta::assert_snapshot!(to_toml(2024, &days[3..]).unwrap(), @r"
+        # Stars earned each day, saved by `fetch progress` for `aoc readme`.
+
+        year = 2024
+
+        [[day]]
+        day = 4
+        stars = 1
+
+        [[day]]
+        day = 5
+        stars = 0
+        ");
+    }
+}
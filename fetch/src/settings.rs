@@ -0,0 +1,231 @@
+//! Settings from `aoc.toml`, found by looking up from the current directory
+//! as far as the workspace root. Paths in it are relative to the directory
+//! it's in, so running from anywhere in the workspace saves to the same
+//! place. Environment variables override the file.
+
+use serde::Deserialize;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+pub const FILE: &str = "aoc.toml";
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    pub base_url: String,
+    pub year: u16,
+    pub user_agent: String,
+    /// Seconds to leave between requests.
+    pub request_interval: u64,
+    /// Commands the page is piped through to make Markdown, separated by
+    /// `|`.
+    pub converter: String,
+    /// Directory inputs, and the pages describing them, are saved to.
+    pub inputs: PathBuf,
+    /// Directory descriptions and their examples are saved to.
+    pub descriptions: PathBuf,
+    /// File names, where `{year}`, `{day}` and `{day:02}` are replaced.
+    pub input_file: String,
+    pub description_file: String,
+    pub examples_file: String,
+    /// The description as fetched, to convert again without fetching.
+    pub page_file: String,
+    /// File the stars earned each day are saved to.
+    pub progress: PathBuf,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            base_url: "https://adventofcode.com".to_string(),
+            year: 2024,
+            user_agent: "advent-of-code-2024 fetch".to_string(),
+            request_interval: 3,
+            converter: "rdrview -H | pandoc -f html -t gfm".to_string(),
+            inputs: PathBuf::from("solutions/src/bin"),
+            descriptions: PathBuf::from("solutions/src/bin"),
+            input_file: "day{day:02}.input".to_string(),
+            description_file: "day{day:02}.md".to_string(),
+            examples_file: "day{day:02}.examples.toml".to_string(),
+            page_file: "day{day:02}.html".to_string(),
+            progress: PathBuf::from("progress.toml"),
+        }
+    }
+}
+
+fn is_workspace_root(dir: &Path) -> bool {
+    fs::read_to_string(dir.join("Cargo.toml"))
+        .is_ok_and(|manifest| manifest.contains("[workspace]"))
+}
+
+/// The settings file for `start`, if there is one, and the directory paths
+/// are relative to: the file's, or otherwise the workspace root.
+fn find(start: &Path) -> (Option<PathBuf>, PathBuf) {
+    for dir in start.ancestors() {
+        let file = dir.join(FILE);
+        if file.is_file() {
+            return (Some(file), dir.to_path_buf());
+        }
+        if is_workspace_root(dir) {
+            return (None, dir.to_path_buf());
+        }
+    }
+    (None, start.to_path_buf())
+}
+
+fn fill(template: &str, year: u16, day: u8) -> String {
+    template
+        .replace("{year}", &year.to_string())
+        .replace("{day:02}", &format!("{day:02}"))
+        .replace("{day}", &day.to_string())
+}
+
+impl Settings {
+    /// Settings for running from `start`.
+    pub fn load(start: &Path) -> Result<Self, String> {
+        let (file, root) = find(start);
+        let mut settings = match &file {
+            Some(path) => {
+                let toml = fs::read_to_string(path)
+                    .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+                toml::from_str(&toml)
+                    .map_err(|e| format!("failed to parse {}: {e}", path.display()))?
+            }
+            None => Settings::default(),
+        };
+        settings.inputs = root.join(&settings.inputs);
+        settings.descriptions = root.join(&settings.descriptions);
+        settings.progress = root.join(&settings.progress);
+        settings.override_with(|name| env::var(name).ok())?;
+        Ok(settings)
+    }
+
+    /// Override settings with the environment variables `var` finds.
+    fn override_with(&mut self, var: impl Fn(&str) -> Option<String>) -> Result<(), String> {
+        if let Some(base_url) = var("AOC_BASE_URL") {
+            self.base_url = base_url;
+        }
+        if let Some(year) = var("AOC_YEAR") {
+            self.year = year
+                .parse()
+                .map_err(|e| format!("AOC_YEAR isn't a year: {e}"))?;
+        }
+        if let Some(user_agent) = var("AOC_USER_AGENT") {
+            self.user_agent = user_agent;
+        }
+        if let Some(converter) = var("AOC_CONVERTER") {
+            self.converter = converter;
+        }
+        Ok(())
+    }
+
+    pub fn input_path(&self, day: u8) -> PathBuf {
+        self.inputs.join(fill(&self.input_file, self.year, day))
+    }
+
+    pub fn description_path(&self, day: u8) -> PathBuf {
+        self.descriptions
+            .join(fill(&self.description_file, self.year, day))
+    }
+
+    pub fn page_path(&self, day: u8) -> PathBuf {
+        self.inputs.join(fill(&self.page_file, self.year, day))
+    }
+
+    pub fn description_url(&self, day: u8) -> String {
+        let base_url = self.base_url.trim_end_matches('/');
+        format!("{base_url}/{}/day/{day}", self.year)
+    }
+
+    pub fn examples_path(&self, day: u8) -> PathBuf {
+        self.descriptions
+            .join(fill(&self.examples_file, self.year, day))
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct Case {
+        template: String,
+        year: u16,
+        day: u8,
+        filled: String,
+    }
+
+    #[derive(Deserialize)]
+    struct Cases {
+        case: Vec<Case>,
+    }
+
+    #[test]
+    fn filling() {
+        // the solutions check theirs against the same cases
+        let cases: Cases = toml::from_str(include_str!("../fixtures/fill.toml")).unwrap();
+        for case in cases.case {
+            assert_eq!(fill(&case.template, case.year, case.day), case.filled);
+        }
+    }
+
+    #[test]
+    fn discovery() {
+        let workspace = env::temp_dir().join(format!("fetch-settings-{}", std::process::id()));
+        let nested = workspace.join("solutions/src");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(workspace.join("Cargo.toml"), "[workspace]\n").unwrap();
+
+        let settings = Settings::load(&nested).unwrap();
+        assert_eq!(settings.inputs, workspace.join("solutions/src/bin"));
+        assert_eq!(settings.progress, workspace.join("progress.toml"));
+        assert_eq!(
+            settings.description_path(3),
+            workspace.join("solutions/src/bin/day03.md")
+        );
+
+        fs::write(
+            workspace.join(FILE),
+            "year = 2023\nrequest_interval = 10\nconverter = \"pandoc -t gfm\"\ninputs = \"inputs\"\n\
+             input_file = \"{year}/{day}.txt\"\n",
+        )
+        .unwrap();
+        let settings = Settings::load(&nested).unwrap();
+        assert_eq!(settings.request_interval, 10);
+        assert_eq!(settings.converter, "pandoc -t gfm");
+        assert_eq!(settings.input_path(3), workspace.join("inputs/2023/3.txt"));
+        assert_eq!(settings.page_path(3), workspace.join("inputs/day03.html"));
+        assert_eq!(
+            settings.examples_path(3),
+            workspace.join("solutions/src/bin/day03.examples.toml")
+        );
+
+        fs::write(workspace.join(FILE), "years = 2023\n").unwrap();
+        assert!(Settings::load(&nested)
+            .unwrap_err()
+            .contains("unknown field `years`"));
+        fs::remove_dir_all(workspace).unwrap();
+    }
+
+    #[test]
+    fn environment() {
+        let mut settings = Settings::default();
+        settings
+            .override_with(|name| match name {
+                "AOC_YEAR" => Some("2022".to_string()),
+                "AOC_USER_AGENT" => Some("me@example.com".to_string()),
+                "AOC_CONVERTER" => Some("pandoc -f html".to_string()),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(settings.year, 2022);
+        assert_eq!(settings.user_agent, "me@example.com");
+        assert_eq!(settings.converter, "pandoc -f html");
+        assert_eq!(settings.base_url, Settings::default().base_url);
+        assert!(settings
+            .override_with(|name| (name == "AOC_YEAR").then(|| "soon".to_string()))
+            .is_err());
+    }
+}
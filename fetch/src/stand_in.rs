@@ -0,0 +1,60 @@
+//! A local HTTP server standing in for Advent of Code in tests, answering
+//! each path with a canned response and keeping track of what was asked for.
+
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+/// A path and the content type and body it's answered with.
+pub type Route = (&'static str, &'static str, String);
+
+pub struct StandIn {
+    pub url: String,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl StandIn {
+    /// Serve `routes` on a free port until the tests finish, answering
+    /// anything else with a 404.
+    pub fn serve(routes: Vec<Route>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(vec![]));
+        let seen = Arc::clone(&requests);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(&stream);
+                let mut request = String::new();
+                reader.read_line(&mut request).unwrap();
+                // the rest of the head, which nothing looks at
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    line.clear();
+                }
+                let path = request.split(' ').nth(1).unwrap_or_default().to_string();
+                let (status, content_type, body) = match routes.iter().find(|r| r.0 == path) {
+                    Some((_, content_type, body)) => ("200 OK", *content_type, body.as_str()),
+                    None => ("404 Not Found", "text/plain", "404 Not Found"),
+                };
+                seen.lock().unwrap().push(path);
+                write!(
+                    stream,
+                    "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\n\
+                     Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+        StandIn { url, requests }
+    }
+
+    /// The paths requested so far, in order.
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
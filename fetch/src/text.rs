@@ -0,0 +1,237 @@
+//! Renders a puzzle page as text to read in a terminal, wrapped to a width,
+//! either plain or with ANSI styles standing in for the page's emphasis,
+//! code and links.
+
+use crate::html::{self, Node};
+
+pub const WIDTH: usize = 80;
+
+const BOLD: (&str, &str) = ("\x1b[1m", "\x1b[22m");
+const CODE: (&str, &str) = ("\x1b[36m", "\x1b[39m");
+const LINK: (&str, &str) = ("\x1b[4m", "\x1b[24m");
+const HEADING: (&str, &str) = ("\x1b[1;32m", "\x1b[0m");
+
+/// `text` between the codes of `style`, if styling at all, keeping any
+/// whitespace at either end outside so the codes stay attached to words.
+fn style(text: String, (start, end): (&str, &str), styled: bool) -> String {
+    let trimmed = text.trim();
+    if !styled || trimmed.is_empty() {
+        return text;
+    }
+    let before = &text[..text.len() - text.trim_start().len()];
+    let after = &text[text.trim_end().len()..];
+    format!("{before}{start}{trimmed}{end}{after}")
+}
+
+/// Inline text, with line breaks only where the page has them unless `pre`.
+fn inline(nodes: &[Node], styled: bool, pre: bool) -> String {
+    nodes
+        .iter()
+        .map(|node| inline_node(node, styled, pre))
+        .collect()
+}
+
+fn inline_node(node: &Node, styled: bool, pre: bool) -> String {
+    let Node::Element { name, children, .. } = node else {
+        let text = node.text();
+        return if pre {
+            text
+        } else {
+            text.replace(char::is_whitespace, " ")
+        };
+    };
+    let inner = inline(children, styled, pre);
+    match name.as_str() {
+        "em" | "i" | "strong" | "b" => style(inner, BOLD, styled),
+        "code" => style(inner, CODE, styled),
+        "a" => style(inner, LINK, styled),
+        "br" => "\n".to_string(),
+        _ => inner,
+    }
+}
+
+/// How wide `text` shows, leaving out escape codes.
+fn visible_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut escape = false;
+    for c in text.chars() {
+        match c {
+            '\x1b' => escape = true,
+            'm' if escape => escape = false,
+            _ if escape => {}
+            _ => width += 1,
+        }
+    }
+    width
+}
+
+/// Wrap `text` to `width` by words, starting lines after the first with
+/// `indent`.
+fn fill(text: &str, width: usize, indent: &str) -> String {
+    let mut lines = vec![];
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            if !line.is_empty() && visible_width(&line) + 1 + visible_width(word) > width {
+                lines.push(line);
+                line = String::new();
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        lines.push(line);
+    }
+    lines.join(&format!("\n{indent}"))
+}
+
+fn is_block(node: &Node) -> bool {
+    matches!(
+        node.name(),
+        Some(
+            "article"
+                | "blockquote"
+                | "div"
+                | "h1"
+                | "h2"
+                | "h3"
+                | "h4"
+                | "h5"
+                | "h6"
+                | "li"
+                | "ol"
+                | "p"
+                | "pre"
+                | "section"
+                | "ul"
+        )
+    )
+}
+
+/// Text blocks for `nodes` fitting in `width`, with runs of inline nodes
+/// between blocks becoming paragraphs.
+fn blocks(nodes: &[Node], styled: bool, width: usize) -> Vec<String> {
+    let mut rendered = vec![];
+    let mut paragraph = String::new();
+    let end_paragraph = |paragraph: &mut String, rendered: &mut Vec<String>| {
+        if !paragraph.trim().is_empty() {
+            rendered.push(fill(paragraph, width, ""));
+        }
+        paragraph.clear();
+    };
+    for node in nodes {
+        if !is_block(node) {
+            paragraph.push_str(&inline_node(node, styled, false));
+            continue;
+        }
+        end_paragraph(&mut paragraph, &mut rendered);
+        let children = node.children();
+        match node.name().unwrap_or_default() {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let heading = fill(&inline(children, false, false), width, "");
+                rendered.push(style(heading, HEADING, styled));
+            }
+            "pre" => {
+                let code = inline(children, styled, true);
+                let lines: Vec<String> = code
+                    .trim_end_matches('\n')
+                    .lines()
+                    .map(|line| format!("    {line}"))
+                    .collect();
+                rendered.push(lines.join("\n"));
+            }
+            "ul" | "ol" => {
+                let ordered = node.name() == Some("ol");
+                let items = children.iter().filter(|child| child.name() == Some("li"));
+                let lines: Vec<String> = items
+                    .enumerate()
+                    .map(|(idx, item)| {
+                        let marker = if ordered {
+                            format!("{}. ", idx + 1)
+                        } else {
+                            "- ".to_string()
+                        };
+                        let indent = " ".repeat(marker.len());
+                        let width = width.saturating_sub(marker.len()).max(1);
+                        let content = blocks(item.children(), styled, width).join("\n");
+                        let content = content.replace('\n', &format!("\n{indent}"));
+                        format!("{marker}{content}")
+                    })
+                    .collect();
+                rendered.push(lines.join("\n"));
+            }
+            "blockquote" => {
+                let quoted = blocks(children, styled, width.saturating_sub(2).max(1));
+                let quoted: Vec<String> = quoted
+                    .join("\n\n")
+                    .lines()
+                    .map(|line| format!("| {line}").trim_end().to_string())
+                    .collect();
+                rendered.push(quoted.join("\n"));
+            }
+            "p" => {
+                let text = inline(children, styled, false);
+                if !text.trim().is_empty() {
+                    rendered.push(fill(&text, width, ""));
+                }
+            }
+            _ => rendered.extend(blocks(children, styled, width)),
+        }
+    }
+    end_paragraph(&mut paragraph, &mut rendered);
+    rendered
+}
+
+/// The puzzle descriptions and answers on the page as text wrapped to
+/// `width`, styled for a terminal if `styled`. Empty if the page doesn't
+/// describe a puzzle.
+pub fn render(html: &str, width: usize, styled: bool) -> String {
+    let document = html::parse(html);
+    let mut text = vec![];
+    for node in html::puzzle(&document) {
+        text.extend(blocks(std::slice::from_ref(node), styled, width));
+    }
+    if text.is_empty() {
+        String::new()
+    } else {
+        text.join("\n\n") + "\n"
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn plain() {
+        insta::assert_snapshot!(render(include_str!("../fixtures/day03.html"), 60, false));
+    }
+
+    #[test]
+    fn styled() {
+        let html = r#"<main><article class="day-desc"><h2>--- Day 9: Test ---</h2>
+            <p>Find <em>the  sum </em>of <code>mul(2,4)</code>, see <a href="/2024">here</a>.</p>
+            <pre><code>x<em>mul(2,4)</em>
+%&amp;</code></pre></article></main>"#;
+        assert_eq!(
+            render(html, WIDTH, true),
+            "\x1b[1;32m--- Day 9: Test ---\x1b[0m\n\n\
+             Find \x1b[1mthe sum\x1b[22m of \x1b[36mmul(2,4)\x1b[39m, see \x1b[4mhere\x1b[24m.\n\n    \
+             \x1b[36mx\x1b[1mmul(2,4)\x1b[22m\n    %&\x1b[39m\n"
+        );
+    }
+
+    #[test]
+    fn wrapping() {
+        assert_eq!(
+            fill("one two three four", 9, "  "),
+            "one two\n  three\n  four"
+        );
+        assert_eq!(
+            fill("\x1b[1mone\x1b[22m two", 7, ""),
+            "\x1b[1mone\x1b[22m two"
+        );
+        assert_eq!(fill("a\nb c", 80, ""), "a\nb c");
+    }
+}
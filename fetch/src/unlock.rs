@@ -0,0 +1,218 @@
+//! When puzzles unlock, and waiting for them to, or between requests so as not
+//! to make them too often. Each day's puzzle unlocks at midnight US Eastern
+//! (UTC-5, as December has no daylight saving).
+
+use std::{
+    io::{self, Write},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Where the time comes from, so waiting can be tested without waiting.
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+    fn sleep(&mut self, duration: Duration);
+}
+
+/// The actual time.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&mut self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// Days since 1970-01-01 of a date in the proleptic Gregorian calendar.
+fn days_since_epoch(year: i64, month: i64, day: i64) -> i64 {
+    // from Howard Hinnant's days_from_civil, with years starting in March
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// When the puzzle for `day` of `year` unlocks.
+pub fn unlock_time(year: u16, day: u8) -> Result<SystemTime, String> {
+    if year < 2015 {
+        return Err(format!("there was no Advent of Code in {year}"));
+    }
+    if !(1..=25).contains(&day) {
+        return Err(format!("there is no day {day}"));
+    }
+    let days = days_since_epoch(year.into(), 12, day.into());
+    let secs = days * 24 * 60 * 60 + 5 * 60 * 60;
+    Ok(UNIX_EPOCH + Duration::from_secs(secs as u64))
+}
+
+/// A duration as hours, minutes and seconds, rounding up so it only reads
+/// zero once it's over.
+pub fn countdown(remaining: Duration) -> String {
+    let secs = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+    let (hours, minutes, secs) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours >= 24 {
+        format!("{}d {:02}:{minutes:02}:{secs:02}", hours / 24, hours % 24)
+    } else {
+        format!("{hours:02}:{minutes:02}:{secs:02}")
+    }
+}
+
+/// Wait for `unlock`, counting down on `out` every second, then for `delay`
+/// more so not everyone waiting asks at the same instant. Returns right away
+/// if `unlock` has passed.
+pub fn wait(
+    clock: &mut impl Clock,
+    unlock: SystemTime,
+    delay: Duration,
+    mut out: impl Write,
+) -> io::Result<()> {
+    let mut waited = false;
+    while let Ok(remaining) = unlock.duration_since(clock.now()) {
+        if remaining.is_zero() {
+            break;
+        }
+        write!(out, "\runlocks in {}", countdown(remaining))?;
+        out.flush()?;
+        waited = true;
+        // to the next whole second left, so the countdown ticks evenly
+        let step = match remaining.subsec_nanos() {
+            0 => Duration::from_secs(1),
+            nanos => Duration::from_nanos(nanos.into()),
+        };
+        clock.sleep(step);
+    }
+    if waited {
+        writeln!(out, "\runlocked, fetching in {delay:.1?}")?;
+        clock.sleep(delay);
+    }
+    Ok(())
+}
+
+/// Spaces requests at least an interval apart.
+#[derive(Debug)]
+pub struct RateLimit {
+    interval: Duration,
+    last: Option<SystemTime>,
+}
+
+impl RateLimit {
+    pub fn new(interval: Duration) -> Self {
+        RateLimit {
+            interval,
+            last: None,
+        }
+    }
+
+    /// Wait until the next request may be made, counting it as made.
+    pub fn wait(&mut self, clock: &mut impl Clock) {
+        if let Some(last) = self.last {
+            if let Ok(remaining) = (last + self.interval).duration_since(clock.now()) {
+                clock.sleep(remaining);
+            }
+        }
+        self.last = Some(clock.now());
+    }
+}
+
+/// A delay of one to five seconds, different each run.
+pub fn random_delay() -> Duration {
+    use std::{
+        collections::hash_map::RandomState,
+        hash::{BuildHasher, Hasher},
+    };
+    let random = RandomState::new().build_hasher().finish();
+    Duration::from_millis(1000 + random % 4000)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    /// A clock that only moves when slept on.
+    struct FakeClock {
+        now: SystemTime,
+        slept: Vec<Duration>,
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> SystemTime {
+            self.now
+        }
+
+        fn sleep(&mut self, duration: Duration) {
+            self.now += duration;
+            self.slept.push(duration);
+        }
+    }
+
+    #[test]
+    fn unlock_times() {
+        let secs = |year, day| {
+            unlock_time(year, day)
+                .unwrap()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs()
+        };
+        // 2024-12-01T05:00:00Z and 2015-12-25T05:00:00Z
+        assert_eq!(secs(2024, 1), 1_733_029_200);
+        assert_eq!(secs(2015, 25), 1_451_019_600);
+        assert!(unlock_time(2014, 1).is_err());
+        assert!(unlock_time(2024, 26).is_err());
+    }
+
+    #[test]
+    fn countdowns() {
+        assert_eq!(countdown(Duration::from_millis(500)), "00:00:01");
+        assert_eq!(countdown(Duration::from_secs(3 * 3600 + 62)), "03:01:02");
+        assert_eq!(countdown(Duration::from_secs(2 * 86400 + 5)), "2d 00:00:05");
+    }
+
+    #[test]
+    fn waiting() {
+        let unlock = unlock_time(2024, 3).unwrap();
+        let mut clock = FakeClock {
+            now: unlock - Duration::from_millis(2500),
+            slept: vec![],
+        };
+        let mut out = vec![];
+        wait(&mut clock, unlock, Duration::from_secs(2), &mut out).unwrap();
+        assert_eq!(clock.now, unlock + Duration::from_secs(2));
+        assert_eq!(
+            clock.slept,
+            [500, 1000, 1000, 2000].map(Duration::from_millis)
+        );
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\runlocks in 00:00:03\runlocks in 00:00:02\runlocks in 00:00:01\
+             \runlocked, fetching in 2.0s\n"
+        );
+
+        let mut out = vec![];
+        wait(&mut clock, unlock, Duration::from_secs(2), &mut out).unwrap();
+        assert!(out.is_empty());
+        assert_eq!(clock.slept.len(), 4);
+    }
+
+    #[test]
+    fn rate_limit() {
+        let mut clock = FakeClock {
+            now: UNIX_EPOCH,
+            slept: vec![],
+        };
+        let mut limit = RateLimit::new(Duration::from_secs(2));
+        limit.wait(&mut clock);
+        limit.wait(&mut clock);
+        clock.now += Duration::from_millis(500);
+        limit.wait(&mut clock);
+        clock.now += Duration::from_secs(3);
+        limit.wait(&mut clock);
+        assert_eq!(clock.slept, [2000, 1500].map(Duration::from_millis));
+    }
+}
@@ -0,0 +1,226 @@
+//! Checks that responses are what was asked for, since Advent of Code answers
+//! with error messages and login pages that would otherwise be saved as if
+//! they were a puzzle.
+
+use crate::html;
+
+/// What came back for a request, whatever its status.
+#[derive(Debug)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: String,
+    pub body: String,
+}
+
+/// Messages Advent of Code sends instead of an input, with what they mean.
+const ERRORS: &[(&str, &str)] = &[
+    (
+        "before it unlocks",
+        "the puzzle hasn't unlocked yet, use --wait to wait for it",
+    ),
+    (
+        "Please log in",
+        "not logged in, the session cookie is probably wrong or expired",
+    ),
+    (
+        "Internal Server Error",
+        "the server failed, the session cookie is probably malformed",
+    ),
+];
+
+/// The meaning of a known error message in `body`, if there is one.
+fn known_error(body: &str) -> Option<&'static str> {
+    ERRORS
+        .iter()
+        .find(|(phrase, _)| body.contains(phrase))
+        .map(|(_, meaning)| *meaning)
+}
+
+/// Whether the response shows the session cookie wasn't accepted: a request
+/// to log in, or a page whose header doesn't name the user.
+pub fn logged_out(response: &Response) -> bool {
+    if response.body.contains("Please log in") {
+        return true;
+    }
+    if !response.content_type.starts_with("text/html") {
+        return false;
+    }
+    let document = html::parse(&response.body);
+    document.find("header").is_some() && html::user(&document).is_none()
+}
+
+fn looks_like_html(body: &str) -> bool {
+    let body = body.trim_start();
+    let start = body.get(..14).unwrap_or(body).to_ascii_lowercase();
+    start.starts_with("<!doctype html") || start.starts_with("<html")
+}
+
+/// Describe what went wrong with a response that isn't a success.
+fn status(response: &Response, what: &str) -> Result<(), String> {
+    if response.status == 200 {
+        return Ok(());
+    }
+    let reason = known_error(&response.body).unwrap_or("the request failed");
+    Err(format!("{what} returned {}: {reason}", response.status))
+}
+
+/// Check `response` is a puzzle input.
+pub fn input(response: &Response) -> Result<(), String> {
+    status(response, "input")?;
+    if let Some(meaning) = known_error(&response.body) {
+        return Err(format!("input is an error message: {meaning}"));
+    }
+    if looks_like_html(&response.body) {
+        return Err("input is an HTML page, probably asking to log in".to_string());
+    }
+    if !response.content_type.starts_with("text/plain") {
+        return Err(format!(
+            "input is {} rather than plain text",
+            response.content_type
+        ));
+    }
+    if response.body.trim().is_empty() {
+        return Err("input is empty".to_string());
+    }
+    Ok(())
+}
+
+/// Check `response` is a page describing a puzzle.
+pub fn description(response: &Response) -> Result<(), String> {
+    status(response, "description")?;
+    if !response.content_type.starts_with("text/html") {
+        return Err(format!(
+            "description is {} rather than HTML",
+            response.content_type
+        ));
+    }
+    let document = html::parse(&response.body);
+    if html::puzzle(&document).is_empty() {
+        let reason = known_error(&response.body).unwrap_or("it has no puzzle in it");
+        return Err(format!("description isn't a puzzle page: {reason}"));
+    }
+    Ok(())
+}
+
+/// Check `response` is a leaderboard, which it isn't if the id is wrong or
+/// the user isn't a member of it.
+pub fn leaderboard(response: &Response) -> Result<(), String> {
+    status(response, "leaderboard")?;
+    if !response.content_type.starts_with("application/json") {
+        return Err(format!(
+            "leaderboard is {} rather than JSON, check its id and that you're a member of it",
+            response.content_type
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn response(status: u16, content_type: &str, body: &str) -> Response {
+        Response {
+            status,
+            content_type: content_type.to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn inputs() {
+        assert_eq!(
+            input(&response(200, "text/plain", "3   4\n4   3\n")),
+            Ok(())
+        );
+        assert_eq!(
+            input(&response(
+                404,
+                "text/plain",
+                "Please don't repeatedly request this endpoint before it unlocks! \
+                 The calendar countdown is synchronized with the server time; \
+                 the link will be enabled on the calendar the instant this puzzle \
+                 becomes available.\n"
+            )),
+            Err("input returned 404: the puzzle hasn't unlocked yet, \
+                 use --wait to wait for it"
+                .to_string())
+        );
+        assert_eq!(
+            input(&response(
+                400,
+                "text/plain",
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+            )),
+            Err("input returned 400: not logged in, \
+                 the session cookie is probably wrong or expired"
+                .to_string())
+        );
+        assert_eq!(
+            input(&response(
+                200,
+                "text/html",
+                "<!DOCTYPE html>\n<html><body><a href=\"/auth/login\">[Log In]</a></body></html>"
+            )),
+            Err("input is an HTML page, probably asking to log in".to_string())
+        );
+        assert_eq!(
+            input(&response(200, "application/json", "{}")),
+            Err("input is application/json rather than plain text".to_string())
+        );
+        assert_eq!(
+            input(&response(200, "text/plain", "\n")),
+            Err("input is empty".to_string())
+        );
+    }
+
+    #[test]
+    fn logging_in() {
+        let page = include_str!("../fixtures/day03.html");
+        assert!(!logged_out(&response(200, "text/html", page)));
+        let anonymous = page.replace("example-user ", "");
+        assert!(logged_out(&response(200, "text/html", &anonymous)));
+        assert!(logged_out(&response(
+            400,
+            "text/plain",
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+        )));
+        assert!(!logged_out(&response(200, "text/plain", "3   4\n")));
+    }
+
+    #[test]
+    fn descriptions() {
+        let page = include_str!("../fixtures/day02.html");
+        assert_eq!(description(&response(200, "text/html", page)), Ok(()));
+        assert_eq!(
+            description(&response(
+                200,
+                "text/html",
+                "<!DOCTYPE html><html><main><p>Please log in.</p></main></html>"
+            )),
+            Err("description isn't a puzzle page: not logged in, \
+                 the session cookie is probably wrong or expired"
+                .to_string())
+        );
+        assert_eq!(
+            description(&response(404, "text/html", "<html>404 Not Found</html>")),
+            Err("description returned 404: the request failed".to_string())
+        );
+        assert_eq!(
+            description(&response(200, "text/plain", "3   4\n")),
+            Err("description is text/plain rather than HTML".to_string())
+        );
+    }
+
+    #[test]
+    fn leaderboards() {
+        assert_eq!(
+            leaderboard(&response(200, "application/json", "{}")),
+            Ok(())
+        );
+        assert_eq!(
+            leaderboard(&response(500, "application/json", "")),
+            Err("leaderboard returned 500: the request failed".to_string())
+        );
+    }
+}
@@ -0,0 +1,62 @@
+//! Compares the general day 1 implementation with the parse-once one, on the
+//! puzzle input and on a synthetic input of ten million lines.
+//!
+//! Run with `cargo bench --bench day01`.
+
+use advent_of_code_2024::day01::{distance, fast::Histograms, generate, similarity, Columns};
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+const INPUT: &str = include_str!("../src/bin/day01.input");
+const SYNTHETIC_LINES: usize = 10_000_000;
+
+/// Median time of `samples` runs of `f`, after a warm-up run.
+fn median<T>(samples: usize, mut f: impl FnMut() -> T) -> Duration {
+    black_box(f());
+    let mut times: Vec<Duration> = (0..samples)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+    times.sort();
+    times[samples / 2]
+}
+
+fn general(input: &str) -> (i64, i64) {
+    let columns = Columns::parse(input).unwrap();
+    let (left, right) = (columns.get(0).unwrap(), columns.get(1).unwrap());
+    (distance(left, right), similarity(left, right))
+}
+
+fn fast(histograms: &mut Histograms, input: &str) -> (i64, i64) {
+    histograms.fill(input.as_bytes()).unwrap();
+    (histograms.distance(), histograms.similarity())
+}
+
+fn main() {
+    let synthetic = generate::sized(2024, SYNTHETIC_LINES);
+    let inputs = [("puzzle", INPUT, 101), ("synthetic", synthetic.as_str(), 5)];
+    let mut histograms = Histograms::new();
+
+    println!(
+        "{:<10} {:>10} {:>12} {:>12} {:>8}",
+        "input", "lines", "general", "fast", "speedup"
+    );
+    for (name, input, samples) in inputs {
+        assert_eq!(general(input), fast(&mut histograms, input));
+        let general = median(samples, || general(input));
+        let fast = median(samples, || fast(&mut histograms, input));
+        println!(
+            "{:<10} {:>10} {:>12} {:>12} {:>7.1}x",
+            name,
+            input.lines().count(),
+            format!("{general:.2?}"),
+            format!("{fast:.2?}"),
+            general.as_secs_f64() / fast.as_secs_f64()
+        );
+    }
+}
@@ -0,0 +1,132 @@
+//! Times parsing and each part of every registered day, stores the timings
+//! for the current commit, and flags phases that got slower since the last
+//! run stored for an ancestor commit.
+//!
+//! Run with `cargo bench --bench days`, passing options after `--`.
+
+use advent_of_code_2024::{
+    bench::{format_nanos, git, Change, Measurement, Run, Sampler, Store},
+    solution::{self, Day, DAYS},
+};
+use clap::Parser;
+use std::{
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+#[derive(Debug, Parser)]
+struct Options {
+    /// Days to benchmark, by default every registered day.
+    #[arg(long = "day")]
+    days: Vec<u8>,
+    /// Number of samples of each phase.
+    #[arg(long, default_value_t = 30)]
+    samples: usize,
+    /// Milliseconds to run each phase before sampling it.
+    #[arg(long, default_value_t = 200)]
+    warm_up: u64,
+    /// How much slower than before a phase's median may get, in percent,
+    /// before it's flagged as a regression.
+    #[arg(long, default_value_t = 10.0)]
+    threshold: f64,
+    /// Benchmark made up inputs of this size instead of the puzzle inputs,
+    /// storing the runs separately for each size.
+    #[arg(long)]
+    size: Option<usize>,
+    /// Seed for made up inputs.
+    #[arg(long, default_value_t = 2024)]
+    seed: u64,
+    /// Don't store this run.
+    #[arg(long)]
+    no_save: bool,
+    /// Passed by `cargo bench`.
+    #[arg(long, hide = true)]
+    bench: bool,
+}
+
+fn main() -> Result<(), String> {
+    let options = Options::parse();
+    let days: Vec<&Day> = if options.days.is_empty() {
+        DAYS.iter().collect()
+    } else {
+        options
+            .days
+            .iter()
+            .map(|day| solution::find(*day))
+            .collect::<Result<_, _>>()?
+    };
+    let sampler = Sampler {
+        warm_up: Duration::from_millis(options.warm_up),
+        samples: options.samples,
+        ..Sampler::default()
+    };
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let store = match options.size {
+        Some(size) => Store::new(root.join(format!("benches/results/size-{size}"))),
+        None => Store::new(root.join("benches/results")),
+    };
+    let commit = git::commit(root)?;
+    let previous = store.previous(&commit, &git::history(root)?)?;
+
+    let mut measurements = vec![];
+    for day in days {
+        let input = match options.size {
+            Some(size) => day.generate(options.seed, size)?,
+            None => day.read_input()?,
+        };
+        measurements.extend(day.bench(&input, &sampler)?);
+    }
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| e.to_string())?
+        .as_secs();
+    let run = Run {
+        commit,
+        timestamp,
+        measurements,
+    };
+
+    let threshold = options.threshold / 100.0;
+    let mut regressions = 0;
+    println!(
+        "{:<4} {:<7} {:>12} {:>12} {:>12}  change",
+        "day", "phase", "median", "stddev", "min"
+    );
+    for Measurement { day, phase, stats } in &run.measurements {
+        let change = previous
+            .as_ref()
+            .and_then(|previous| previous.get(*day, *phase))
+            .map(|before| Change::between(before, stats));
+        let change = match change {
+            Some(change) if change.regressed(threshold) => {
+                regressions += 1;
+                format!("{:+.1}% regressed", change.relative() * 100.0)
+            }
+            Some(change) => format!("{:+.1}%", change.relative() * 100.0),
+            None => String::new(),
+        };
+        let line = format!(
+            "{:<4} {:<7} {:>12} {:>12} {:>12}  {}",
+            day,
+            phase.to_string(),
+            format_nanos(stats.median),
+            format_nanos(stats.stddev),
+            format_nanos(stats.min),
+            change
+        );
+        println!("{}", line.trim_end());
+    }
+
+    match &previous {
+        Some(previous) => println!(
+            "{regressions} regressions since {} (threshold {}%)",
+            previous.commit, options.threshold
+        ),
+        None => println!("no earlier run to compare with"),
+    }
+    if !options.no_save {
+        println!("saved run to {}", store.save(&run)?.display());
+    }
+    Ok(())
+}
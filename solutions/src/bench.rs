@@ -0,0 +1,354 @@
+//! Timing each phase of a day's solution, storing the timings of a run per
+//! git commit, and comparing them with an earlier run to spot regressions.
+
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt, fs,
+    hint::black_box,
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    /// Reading the input file, which is only timed by the runner.
+    Read,
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Read => write!(f, "read input"),
+            Phase::Parse => write!(f, "parse"),
+            Phase::Part1 => write!(f, "part 1"),
+            Phase::Part2 => write!(f, "part 2"),
+        }
+    }
+}
+
+/// How long to spend measuring something.
+#[derive(Debug, Clone)]
+pub struct Sampler {
+    /// Time spent running before measuring, which also decides how many
+    /// iterations make up a sample.
+    pub warm_up: Duration,
+    pub samples: usize,
+    /// Roughly how long each sample takes, so that fast functions are run
+    /// many times per sample rather than timing a single call.
+    pub sample_time: Duration,
+}
+
+impl Default for Sampler {
+    fn default() -> Self {
+        Sampler {
+            warm_up: Duration::from_millis(200),
+            samples: 30,
+            sample_time: Duration::from_millis(10),
+        }
+    }
+}
+
+impl Sampler {
+    /// Time calls to `f`, in nanoseconds per call.
+    pub fn measure<T>(&self, mut f: impl FnMut() -> T) -> Stats {
+        let start = Instant::now();
+        let mut warm_up_calls: u32 = 0;
+        while warm_up_calls == 0 || start.elapsed() < self.warm_up {
+            black_box(f());
+            warm_up_calls += 1;
+        }
+        let per_call = start.elapsed() / warm_up_calls;
+        let calls = (self.sample_time.as_nanos() / per_call.as_nanos().max(1)).max(1) as u32;
+
+        let samples: Vec<f64> = (0..self.samples.max(1))
+            .map(|_| {
+                let start = Instant::now();
+                for _ in 0..calls {
+                    black_box(f());
+                }
+                start.elapsed().as_nanos() as f64 / calls as f64
+            })
+            .collect();
+        Stats::new(samples)
+    }
+}
+
+/// Summary of samples, in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub median: f64,
+    pub mean: f64,
+    pub stddev: f64,
+    pub min: f64,
+    pub max: f64,
+    pub samples: usize,
+}
+
+impl Stats {
+    pub fn new(mut samples: Vec<f64>) -> Self {
+        samples.sort_by(f64::total_cmp);
+        let len = samples.len();
+        let median = if len.is_multiple_of(2) {
+            (samples[len / 2 - 1] + samples[len / 2]) / 2.0
+        } else {
+            samples[len / 2]
+        };
+        let mean = samples.iter().sum::<f64>() / len as f64;
+        let variance = samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / len as f64;
+        Stats {
+            median,
+            mean,
+            stddev: variance.sqrt(),
+            min: samples[0],
+            max: samples[len - 1],
+            samples: len,
+        }
+    }
+}
+
+/// Format nanoseconds with a sensible unit.
+pub fn format_nanos(nanos: f64) -> String {
+    format!("{:.2?}", Duration::from_secs_f64(nanos / 1e9))
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Measurement {
+    pub day: u8,
+    pub phase: Phase,
+    pub stats: Stats,
+}
+
+/// Timings of every benchmarked phase at a commit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Run {
+    /// Commit hash, suffixed with `-dirty` when there were uncommitted
+    /// changes.
+    pub commit: String,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub measurements: Vec<Measurement>,
+}
+
+impl Run {
+    pub fn get(&self, day: u8, phase: Phase) -> Option<&Stats> {
+        self.measurements
+            .iter()
+            .find(|m| m.day == day && m.phase == phase)
+            .map(|m| &m.stats)
+    }
+}
+
+/// How a phase's median changed between two runs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Change {
+    pub before: f64,
+    pub after: f64,
+}
+
+impl Change {
+    pub fn between(before: &Stats, after: &Stats) -> Self {
+        Change {
+            before: before.median,
+            after: after.median,
+        }
+    }
+
+    /// Relative change, e.g. 0.1 for 10% slower.
+    pub fn relative(&self) -> f64 {
+        self.after / self.before - 1.0
+    }
+
+    /// Whether the phase got slower by more than `threshold`, relative to
+    /// before.
+    pub fn regressed(&self, threshold: f64) -> bool {
+        self.relative() > threshold
+    }
+}
+
+/// Directory of runs, stored as `<commit>.json`.
+#[derive(Debug, Clone)]
+pub struct Store {
+    dir: PathBuf,
+}
+
+impl Store {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Store { dir: dir.into() }
+    }
+
+    fn path(&self, commit: &str) -> PathBuf {
+        self.dir.join(format!("{commit}.json"))
+    }
+
+    /// Save `run`, keeping measurements of other days from an earlier run at
+    /// the same commit so benchmarking days one at a time adds up.
+    pub fn save(&self, run: &Run) -> Result<PathBuf, String> {
+        let mut run = run.clone();
+        if let Some(earlier) = self.load(&run.commit)? {
+            let days: Vec<u8> = run.measurements.iter().map(|m| m.day).collect();
+            let kept = earlier
+                .measurements
+                .into_iter()
+                .filter(|m| !days.contains(&m.day));
+            run.measurements.extend(kept);
+            run.measurements.sort_by_key(|m| m.day);
+        }
+        let path = self.path(&run.commit);
+        let write_error = |e: String| format!("failed to write {}: {e}", path.display());
+        fs::create_dir_all(&self.dir).map_err(|e| write_error(e.to_string()))?;
+        let json = serde_json::to_string_pretty(&run).map_err(|e| write_error(e.to_string()))?;
+        fs::write(&path, json + "\n").map_err(|e| write_error(e.to_string()))?;
+        Ok(path)
+    }
+
+    pub fn load(&self, commit: &str) -> Result<Option<Run>, String> {
+        let path = self.path(commit);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let json = fs::read_to_string(&path)
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+        serde_json::from_str(&json)
+            .map(Some)
+            .map_err(|e| format!("failed to parse {}: {e}", path.display()))
+    }
+
+    /// The run to compare `commit` with: the newest of `history` with a
+    /// stored run, other than `commit` itself. `history` is newest first, as
+    /// listed by [`git::history`].
+    pub fn previous(&self, commit: &str, history: &[String]) -> Result<Option<Run>, String> {
+        for candidate in history.iter().filter(|c| *c != commit) {
+            if let Some(run) = self.load(candidate)? {
+                return Ok(Some(run));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Just enough git to name runs after commits.
+pub mod git {
+    use super::*;
+
+    fn git(dir: &Path, args: &[&str]) -> Result<String, String> {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .map_err(|e| format!("failed to run git: {e}"))?;
+        if !output.status.success() {
+            return Err(format!(
+                "git {} failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// The checked out commit, suffixed with `-dirty` if tracked files have
+    /// changed since.
+    pub fn commit(dir: &Path) -> Result<String, String> {
+        let commit = git(dir, &["rev-parse", "HEAD"])?;
+        let changes = git(dir, &["status", "--porcelain", "--untracked-files=no"])?;
+        Ok(if changes.is_empty() {
+            commit
+        } else {
+            format!("{commit}-dirty")
+        })
+    }
+
+    /// The checked out commit and its ancestors, newest first. A dirty
+    /// working tree's nearest ancestor is its own commit.
+    pub fn history(dir: &Path) -> Result<Vec<String>, String> {
+        Ok(git(dir, &["rev-list", "HEAD"])?
+            .lines()
+            .map(String::from)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn stats(median: f64) -> Stats {
+        Stats::new(vec![median])
+    }
+
+    #[test]
+    fn summarizes_samples() {
+        let stats = Stats::new(vec![4.0, 1.0, 3.0, 2.0]);
+        assert_eq!(stats.median, 2.5);
+        assert_eq!(stats.mean, 2.5);
+        assert_eq!((stats.min, stats.max, stats.samples), (1.0, 4.0, 4));
+        assert!((stats.stddev - 1.25f64.sqrt()).abs() < 1e-9);
+        assert_eq!(Stats::new(vec![3.0, 1.0, 2.0]).median, 2.0);
+    }
+
+    #[test]
+    fn measures_every_sample() {
+        let sampler = Sampler {
+            warm_up: Duration::ZERO,
+            samples: 5,
+            sample_time: Duration::ZERO,
+        };
+        let mut calls = 0;
+        let stats = sampler.measure(|| calls += 1);
+        assert_eq!(stats.samples, 5);
+        assert_eq!(calls, 6);
+    }
+
+    #[test]
+    fn flags_regressions() {
+        let change = Change::between(&stats(100.0), &stats(125.0));
+        assert!((change.relative() - 0.25).abs() < 1e-9);
+        assert!(change.regressed(0.1));
+        assert!(!change.regressed(0.3));
+        assert!(!Change::between(&stats(100.0), &stats(50.0)).regressed(0.1));
+    }
+
+    #[test]
+    fn previous_run() {
+        let dir = std::env::temp_dir().join(format!("aoc-bench-{}", std::process::id()));
+        let store = Store::new(&dir);
+        let run = |commit: &str| Run {
+            commit: commit.to_string(),
+            timestamp: 0,
+            measurements: vec![Measurement {
+                day: 1,
+                phase: Phase::Part1,
+                stats: stats(1.0),
+            }],
+        };
+        store.save(&run("a")).unwrap();
+        store.save(&run("c")).unwrap();
+        let history = ["c", "b", "a"].map(String::from);
+
+        assert_eq!(store.load("c").unwrap(), Some(run("c")));
+        assert_eq!(store.previous("c", &history).unwrap(), Some(run("a")));
+        assert_eq!(store.previous("c-dirty", &history).unwrap(), Some(run("c")));
+        assert_eq!(store.previous("a", &history[2..]).unwrap(), None);
+        assert_eq!(run("a").get(1, Phase::Part1), Some(&stats(1.0)));
+
+        let mut day2 = run("c");
+        day2.measurements[0].day = 2;
+        store.save(&day2).unwrap();
+        let days: Vec<u8> = store
+            .load("c")
+            .unwrap()
+            .unwrap()
+            .measurements
+            .iter()
+            .map(|m| m.day)
+            .collect();
+        assert_eq!(days, vec![1, 2]);
+        assert_eq!(run("a").get(1, Phase::Part2), None);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,160 @@
+//! Run any of the solved days from one place.
+
+use advent_of_code_2024::{
+    day01, day02, day03,
+    progress::{self, Progress},
+    runner::{totals, Report},
+    scaffold,
+    settings::Settings,
+    solution::{self, Day, DAYS},
+};
+use clap::{Parser, Subcommand};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+#[derive(Debug, Parser)]
+struct Options {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Solve days on their puzzle input, timing each phase.
+    Run {
+        /// Days to solve, by default every solved day.
+        days: Vec<u8>,
+        /// Finish with a table of every day's times and their total.
+        #[arg(long)]
+        total: bool,
+        /// Milliseconds a day may take before it's flagged in the total.
+        #[arg(long, default_value_t = 1.0)]
+        budget: f64,
+    },
+    /// Make up an input shaped like a day's puzzle input, which unlike the
+    /// puzzle input can be shared.
+    Generate {
+        #[command(subcommand)]
+        day: Generator,
+        /// The same seed always makes the same input.
+        #[arg(long, global = true, default_value_t = 2024)]
+        seed: u64,
+        /// File to write the input to, instead of printing it.
+        #[arg(long, short, global = true)]
+        output: Option<PathBuf>,
+    },
+    /// Start a new day from templates, registered with the runner, after
+    /// fetching its description and input.
+    New {
+        day: u8,
+        /// Title of the puzzle, by default the one in its description.
+        #[arg(long)]
+        title: Option<String>,
+        /// Don't fetch the description and input.
+        #[arg(long)]
+        no_fetch: bool,
+    },
+    /// Update the progress table in the README, from the stars saved by
+    /// `fetch progress` and a run of every solved day.
+    Readme,
+}
+
+#[derive(Debug, Subcommand)]
+enum Generator {
+    /// Location ID lists.
+    Day01(day01::generate::Config),
+    /// Level reports.
+    Day02(day02::generate::Config),
+    /// Corrupted memory.
+    Day03(day03::generate::Config),
+}
+
+/// The days given, or every day if none were.
+fn days(days: &[u8]) -> Result<Vec<&'static Day>, String> {
+    if days.is_empty() {
+        return Ok(DAYS.iter().collect());
+    }
+    days.iter().map(|day| solution::find(*day)).collect()
+}
+
+fn main() -> Result<(), String> {
+    match Options::parse().command {
+        Command::Run {
+            days: selected,
+            total,
+            budget,
+        } => {
+            let mut reports: Vec<Report> = vec![];
+            for (idx, day) in days(&selected)?.into_iter().enumerate() {
+                if idx > 0 {
+                    println!();
+                }
+                println!("day {}: {}", day.day, day.title);
+                let report = day.run(None)?;
+                report.print();
+                reports.push(report);
+            }
+            if total {
+                println!();
+                for row in totals(&reports, Duration::from_secs_f64(budget / 1000.0)) {
+                    println!("{row}");
+                }
+            }
+        }
+        Command::Generate { day, seed, output } => {
+            let input = match day {
+                Generator::Day01(config) => day01::generate::generate(&config, seed),
+                Generator::Day02(config) => day02::generate::generate(&config, seed),
+                Generator::Day03(config) => day03::generate::generate(&config, seed),
+            };
+            match output {
+                Some(path) => fs::write(&path, input)
+                    .map_err(|e| format!("failed to write {}: {e}", path.display()))?,
+                None => print!("{input}"),
+            }
+        }
+        Command::New {
+            day,
+            title,
+            no_fetch,
+        } => {
+            let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+            scaffold::check(root, day)?;
+            if !no_fetch {
+                if let Err(e) = scaffold::fetch(root, day) {
+                    println!("didn't fetch day {day}: {e}");
+                }
+            }
+            for path in scaffold::scaffold(root, day, title.as_deref())? {
+                println!("wrote {}", path.display());
+            }
+            if let Err(e) = scaffold::format(root) {
+                println!("didn't format the registry: {e}");
+            }
+        }
+        Command::Readme => {
+            let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+            let workspace = root.parent().ok_or("the crate isn't in a workspace")?;
+            let settings = Settings::load(workspace)?;
+            let progress = Progress::load(&workspace.join(&settings.progress))?;
+            let rows = progress::rows(&progress, &settings, workspace, |day| {
+                day.run(None).ok().map(|report| report.total())
+            });
+            let table = progress::table(&rows, progress.year.unwrap_or(settings.year));
+            let path = workspace.join("README.md");
+            let readme = fs::read_to_string(&path)
+                .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+            fs::write(&path, progress::replace(&readme, &table)?)
+                .map_err(|e| format!("failed to write {}: {e}", path.display()))?;
+            println!(
+                "updated the progress of {} days in {}",
+                rows.len(),
+                path.display()
+            );
+        }
+    }
+    Ok(())
+}
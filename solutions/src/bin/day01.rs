@@ -1,55 +1,56 @@
-use std::collections::HashMap;
-
-fn main() {
-    let input = include_str!("day01.input");
-
-    println!("part 1: {}", part1(input));
-    println!("part 2: {}", part2(input));
+use advent_of_code_2024::{
+    day01::{distance, fast::Histograms, similarity, Columns, ParseError, Puzzle},
+    solution::Day,
+};
+use clap::Parser;
+use std::{fs, path::PathBuf};
+
+/// Compare the location lists in two columns.
+#[derive(Debug, Parser)]
+struct Options {
+    /// Input to read instead of the puzzle input.
+    #[arg(long)]
+    input: Option<PathBuf>,
+    /// Column of the left list, counting from 0.
+    #[arg(long, default_value_t = 0)]
+    left: usize,
+    /// Column of the right list, counting from 0.
+    #[arg(long, default_value_t = 1)]
+    right: usize,
+    /// Use the parse-once implementation, which only reads two columns of
+    /// IDs with at most five digits.
+    #[arg(long, conflicts_with_all = ["left", "right"])]
+    fast: bool,
 }
 
-fn parse(input: &str) -> (Vec<i64>, Vec<i64>) {
-    let pairs = input.lines().map(
-        |line| match line.split_whitespace().collect::<Vec<&str>>()[..] {
-            [first, second] => (
-                first.parse::<i64>().unwrap(),
-                second.parse::<i64>().unwrap(),
-            ),
-            _ => panic!("failed"),
-        },
-    );
-    pairs.unzip()
-}
-
-fn part1(input: &str) -> i64 {
-    let (mut left, mut right) = parse(input);
-    left.sort();
-    right.sort();
-
-    let total_distance: i64 = left
-        .iter()
-        .zip(right.iter())
-        .map(|(left, right)| (left - right).abs())
-        .sum();
-
-    total_distance
-}
-
-fn part2(input: &str) -> i64 {
-    let (left, right) = parse(input);
-    let mut counts: HashMap<i64, i64> = HashMap::new();
-    right.iter().for_each(|num| {
-        counts
-            .entry(*num)
-            .and_modify(|count| *count += 1)
-            .or_insert(1);
-    });
-    let total_similarity: i64 = left
-        .iter()
-        .map(|search| {
-            let count = counts.get(search).unwrap_or(&0);
-            count * search
-        })
-        .sum();
-
-    total_similarity
+fn main() -> Result<(), String> {
+    let options = Options::parse();
+    let day = Day::new::<Puzzle>();
+    if !options.fast && (options.left, options.right) == (0, 1) {
+        day.run(options.input.as_deref())?.print();
+        return Ok(());
+    }
+    let input = match &options.input {
+        Some(path) => fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?,
+        None => day.read_input()?,
+    };
+
+    if options.fast {
+        let mut histograms = Histograms::new();
+        histograms.fill(input.as_bytes())?;
+        println!("part 1: {}", histograms.distance());
+        println!("part 2: {}", histograms.similarity());
+        return Ok(());
+    }
+
+    let columns = Columns::parse(&input).map_err(|errors| {
+        let errors: Vec<String> = errors.iter().map(ParseError::to_string).collect();
+        errors.join("\n")
+    })?;
+    let (left, right) = (columns.get(options.left)?, columns.get(options.right)?);
+
+    println!("part 1: {}", distance(left, right));
+    println!("part 2: {}", similarity(left, right));
+    Ok(())
 }
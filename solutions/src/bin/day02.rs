@@ -1,111 +1,115 @@
-#![feature(iter_map_windows)]
-use std::cmp::Ordering;
+use advent_of_code_2024::{
+    day02::{Direction, Puzzle, Reports, SafetyPolicy},
+    solution::Day,
+};
+use clap::Parser;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
-#[derive(Debug)]
-struct Levels(Vec<i64>);
-
-impl Levels {
-    fn parse(line: &str) -> Self {
-        Self(
-            line.split_whitespace()
-                .filter_map(|level| level.parse::<i64>().ok())
-                .collect(),
-        )
-    }
-
-    fn monotonic(&self) -> bool {
-        let mut ordering: Vec<Ordering> = self
-            .0
-            .iter()
-            .map_windows(|[a, b]| a.partial_cmp(b).unwrap())
-            .collect();
-        ordering.dedup();
-        ordering == vec![Ordering::Less] || ordering == vec![Ordering::Greater]
-    }
-
-    fn adjacency_check(&self) -> bool {
-        self.0
-            .iter()
-            .map_windows(|[a, b]| (*a - *b).abs())
-            .all(|d| (1..=3).contains(&d))
-    }
-
-    fn safe(&self) -> bool {
-        self.monotonic() && self.adjacency_check()
+/// Print rows as a table with aligned columns.
+fn print_table(rows: &[[String; 6]]) {
+    let mut widths = [0; 6];
+    for row in rows {
+        for (width, column) in widths.iter_mut().zip(row) {
+            *width = (*width).max(column.len());
+        }
     }
-
-    fn remove_index(&self, removed_index: usize) -> Self {
-        let levels = self
-            .0
+    for row in rows {
+        let line: Vec<String> = row
             .iter()
-            .enumerate()
-            .filter_map(|(idx, lvl)| {
-                if idx == removed_index {
-                    None
-                } else {
-                    Some(*lvl)
-                }
-            })
+            .zip(widths)
+            .map(|(column, width)| format!("{column:width$}"))
             .collect();
-        Self(levels)
-    }
-
-    // part two
-    fn dampened_safe(&self) -> bool {
-        let mut indexes = 0..self.0.len();
-        self.safe() || indexes.any(|idx| self.remove_index(idx).safe())
+        println!("{}", line.join("  ").trim_end());
     }
 }
 
-#[derive(Debug)]
-struct Reports(Vec<Levels>);
-
-impl Reports {
-    fn parse(input: &str) -> Self {
-        Self(input.lines().map(Levels::parse).collect())
-    }
+/// Write rows as CSV. No column contains commas or quotes, so nothing needs
+/// escaping.
+fn write_csv(rows: &[[String; 6]], path: &Path) -> Result<(), String> {
+    let csv: String = rows.iter().map(|row| row.join(",") + "\n").collect();
+    fs::write(path, csv).map_err(|e| format!("failed to write {}: {e}", path.display()))
 }
 
-fn part1(input: &Reports) -> usize {
-    input.0.iter().filter(|levels| levels.safe()).count()
+/// Count safe reports, and also under a custom policy when any of its rules
+/// are given.
+#[derive(Debug, Parser)]
+struct Options {
+    /// Input to read instead of the puzzle input.
+    #[arg(long)]
+    input: Option<PathBuf>,
+    /// Print why each report is safe or unsafe, under the custom policy or
+    /// otherwise part 2's.
+    #[arg(long)]
+    explain: bool,
+    /// Write the explanation of each report to a CSV file.
+    #[arg(long)]
+    csv: Option<PathBuf>,
+    /// Smallest allowed difference between adjacent levels.
+    #[arg(long)]
+    min_step: Option<i64>,
+    /// Largest allowed difference between adjacent levels.
+    #[arg(long)]
+    max_step: Option<i64>,
+    /// Which way levels must move.
+    #[arg(long, value_enum)]
+    direction: Option<Direction>,
+    /// Allow adjacent levels to be equal.
+    #[arg(long)]
+    plateaus: bool,
+    /// How many levels the Problem Dampener may remove.
+    #[arg(long)]
+    budget: Option<usize>,
 }
 
-fn part2(input: &Reports) -> usize {
-    input
-        .0
-        .iter()
-        .filter(|levels| levels.dampened_safe())
-        .count()
+impl Options {
+    /// The custom policy, based on part 1's, if any rules were given.
+    fn policy(&self) -> Option<SafetyPolicy> {
+        let custom = self.min_step.is_some()
+            || self.max_step.is_some()
+            || self.direction.is_some()
+            || self.plateaus
+            || self.budget.is_some();
+        let default = SafetyPolicy::PART1;
+        custom.then(|| SafetyPolicy {
+            step: self.min_step.unwrap_or(*default.step.start())
+                ..=self.max_step.unwrap_or(*default.step.end()),
+            direction: self.direction.unwrap_or(default.direction),
+            plateaus: self.plateaus,
+            budget: self.budget.unwrap_or(default.budget),
+        })
+    }
 }
 
-fn main() {
-    let input = Reports::parse(include_str!("day02.input"));
-
-    println!("part 1: {}", part1(&input));
-    println!("part 2: {}", part2(&input));
-}
+fn main() -> Result<(), String> {
+    let options = Options::parse();
+    let day = Day::new::<Puzzle>();
+    day.run(options.input.as_deref())?.print();
 
-#[cfg(test)]
-pub mod tests {
-    use super::*;
+    let policy = options.policy();
+    if policy.is_none() && !options.explain && options.csv.is_none() {
+        return Ok(());
+    }
+    let input = match &options.input {
+        Some(path) => fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?,
+        None => day.read_input()?,
+    };
+    let input = Reports::parse(&input);
+    if let Some(policy) = &policy {
+        println!("custom: {} ({:?})", input.count_safe(policy), policy);
+    }
 
-    #[test]
-    fn example_part1() {
-        let safety: Vec<bool> = Reports::parse(
-            r#"
-7 6 4 2 1
-1 2 7 8 9
-9 7 6 2 1
-1 3 2 4 5
-8 6 4 4 1
-1 3 6 7 9
-"#
-            .trim(),
-        )
-        .0
-        .iter()
-        .map(|l| l.safe())
-        .collect();
-        assert_eq!(safety, vec![true, false, false, false, false, true]);
+    if options.explain || options.csv.is_some() {
+        let rows = input.explain(policy.as_ref().unwrap_or(&SafetyPolicy::PART2));
+        if options.explain {
+            print_table(&rows);
+        }
+        if let Some(path) = &options.csv {
+            write_csv(&rows, path)?;
+        }
     }
+    Ok(())
 }
@@ -0,0 +1,105 @@
+//! Export a trace of tokenizing and parsing day 3 input, either as a single
+//! HTML page that replays it step by step in a browser without needing
+//! nannou, or as JSON Lines for other tools.
+
+use advent_of_code_2024::day03::{self, Trace};
+use clap::{Parser, ValueEnum};
+use serde::Serialize;
+use std::{
+    fs::{self, File},
+    io::BufWriter,
+    path::PathBuf,
+};
+
+/// Page with the player's styles and scripts, and a placeholder for the data.
+const TEMPLATE: &str = include_str!("../../templates/day03.html");
+const PLACEHOLDER: &str = "/*DATA*/null";
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Format {
+    /// A page replaying the trace.
+    Html,
+    /// One JSON encoded trace event per line.
+    Jsonl,
+}
+
+#[derive(Debug, Parser)]
+struct Options {
+    /// Memory to trace, instead of the puzzle description's example for
+    /// `--part`.
+    #[arg(long)]
+    input: Option<PathBuf>,
+    /// A previously exported JSON Lines trace, used instead of tracing an
+    /// input. It has the input it traces in it.
+    #[arg(long, conflicts_with = "input")]
+    trace: Option<PathBuf>,
+    /// Which part's rules to parse with.
+    #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+    #[arg(long, value_enum, default_value_t = Format::Html)]
+    format: Format,
+    /// Where to write the export, by default `day03.html` or `day03.jsonl`.
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+}
+
+/// Everything the player needs, embedded in the page as JSON.
+#[derive(Serialize)]
+struct Export<'a> {
+    input: &'a str,
+    trace: &'a Trace,
+}
+
+fn render(input: &str, trace: &Trace) -> Result<String, String> {
+    let data = serde_json::to_string(&Export { input, trace }).map_err(|e| e.to_string())?;
+    // `<` only appears inside JSON strings, where escaping it keeps the input
+    // from closing the script element
+    let data = data.replace('<', "\\u003c");
+    Ok(TEMPLATE.replace(PLACEHOLDER, &data))
+}
+
+fn main() -> Result<(), String> {
+    let options = Options::parse();
+    let (input, trace) = day03::load_trace(
+        options.trace.as_deref(),
+        options.input.as_deref(),
+        options.part,
+    )?;
+
+    let output = match (&options.output, options.format) {
+        (Some(output), _) => output.clone(),
+        (None, Format::Html) => PathBuf::from("day03.html"),
+        (None, Format::Jsonl) => PathBuf::from("day03.jsonl"),
+    };
+    let write_error = |e| format!("failed to write {}: {e}", output.display());
+    match options.format {
+        Format::Html => {
+            fs::write(&output, render(&input, &trace)?).map_err(|e| write_error(e.to_string()))?
+        }
+        Format::Jsonl => {
+            let file = File::create(&output).map_err(|e| write_error(e.to_string()))?;
+            trace
+                .write_jsonl(&input, BufWriter::new(file))
+                .map_err(write_error)?;
+        }
+    }
+    println!("saved trace to {}", output.display());
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use advent_of_code_2024::day03::{Instructions, ParserConfig};
+
+    #[test]
+    fn embeds_trace() {
+        let input = "mul(1,2)</script><!--mul(3,4)";
+        let (_, trace) = Instructions::parse(input, ParserConfig::Part2);
+        let page = render(input, &trace).unwrap();
+        assert!(!page.contains(PLACEHOLDER));
+        assert_eq!(page.matches("</script>").count(), 1);
+        assert!(!page.contains("<!--mul"));
+        assert!(page.contains(r#""evaluation":"emitted: Mul(1, 2)""#));
+    }
+}
@@ -0,0 +1,398 @@
+use advent_of_code_2024::day03::{self, Mul, ParserState, Trace, TraceEvent};
+use clap::Parser;
+use nannou::{color::rgb_u32, prelude::*};
+use std::{ops::Range, path::PathBuf};
+
+mod theme;
+mod timeline;
+
+use theme::{mix, Theme};
+use timeline::{ease_in_out_quad, ease_out_cubic, progress, Timeline, PULSE, TRANSITION};
+
+const PADDING: f32 = 12.0;
+const BLOCK_SIZE: f32 = 64.0;
+const BLOCKS_PER_ROW: i32 = 8;
+const FONT_SIZE_MIN: u32 = 24;
+const FONT_SIZE_MAX: u32 = 32;
+const STATE_FONT_SIZE: u32 = 18;
+
+/// Nodes of the parser state machine, in the order they're drawn.
+const STATE_NODES: [&str; 5] = ["empty", "mul(", "mul(a", "mul(a,", "mul(a,b"];
+const STATE_NODE_HEIGHT: f32 = 40.0;
+const LINE_HEIGHT: f32 = 28.0;
+
+/// Font embedded in the binary, used unless `--font` is given.
+const FONT: &[u8] = include_bytes!("../../../fonts/DejaVuSansMono.ttf");
+
+/// Visualize tokenizing and parsing the day 3 example.
+#[derive(Debug, Parser)]
+struct Options {
+    /// Name of a built-in theme (dark, light), or path to a TOML palette.
+    #[arg(long, default_value = "dark")]
+    theme: String,
+    /// Font file to use instead of the bundled font.
+    #[arg(long)]
+    font: Option<PathBuf>,
+    /// Memory to visualize, instead of the puzzle description's example
+    /// for `--part`.
+    #[arg(long)]
+    input: Option<PathBuf>,
+    /// A JSON Lines trace, as written by `day03_export`, to replay instead of
+    /// tracing an input. It has the input it traces in it.
+    #[arg(long, conflicts_with = "input")]
+    trace: Option<PathBuf>,
+    /// Which part's rules to parse with.
+    #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+    /// Frames captured per second of animation.
+    #[arg(long, default_value_t = 60.0)]
+    fps: f32,
+}
+
+/// Load the font from `path` if given, falling back to the bundled font and
+/// then to nannou's default font.
+fn load_font(path: Option<&PathBuf>) -> text::Font {
+    if let Some(path) = path {
+        match text::font::from_file(path) {
+            Ok(font) => return font,
+            Err(e) => eprintln!("failed to load font {}: {e:?}", path.display()),
+        }
+    }
+    text::Font::from_bytes(FONT).unwrap_or_else(|e| {
+        eprintln!("failed to load bundled font: {e}");
+        text::font::default_notosans()
+    })
+}
+
+struct Model {
+    input: String,
+    trace: Trace,
+    timeline: Timeline,
+    fps: f32,
+    theme: Theme,
+    font: text::Font,
+}
+
+fn main() {
+    nannou::app(model)
+        .event(event)
+        .update(update)
+        .view(view)
+        .run();
+}
+
+fn update(_app: &App, _model: &mut Model, _update: Update) {}
+
+fn event(_app: &App, _model: &mut Model, _event: Event) {}
+
+fn model(app: &App) -> Model {
+    let options = Options::parse();
+    let theme = Theme::load(&options.theme).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        std::process::exit(1);
+    });
+    app.new_window()
+        .size_pixels(720, 1280)
+        // .fullscreen()
+        .build()
+        .unwrap();
+    let (input, trace) = day03::load_trace(
+        options.trace.as_deref(),
+        options.input.as_deref(),
+        options.part,
+    )
+    .unwrap_or_else(|e| {
+        eprintln!("{e}");
+        std::process::exit(1);
+    });
+    let timeline = Timeline::new(&trace);
+    println!(
+        "capturing {} frames ({:.1}s at {} fps)",
+        timeline.frames(options.fps),
+        timeline.duration(),
+        options.fps
+    );
+    Model {
+        input,
+        timeline,
+        trace,
+        fps: options.fps,
+        theme,
+        font: load_font(options.font.as_ref()),
+    }
+}
+
+/// Input positions highlighted while `event` is on screen: the tokenizer
+/// cursor, or the token being parsed.
+fn active_span(event: &TraceEvent) -> Range<usize> {
+    match event {
+        TraceEvent::TokenizerEvent { pos, .. } => *pos..*pos + 1,
+        TraceEvent::ParserEvent { pos, token, .. } => *pos..*pos + token.source_len(),
+    }
+}
+
+/// Index into [`STATE_NODES`] of the node drawn for `state`.
+fn state_node(state: &ParserState) -> usize {
+    match state {
+        ParserState::Empty { .. } => 0,
+        ParserState::MulStart { .. } => 1,
+        ParserState::MulFirstArg { .. } => 2,
+        ParserState::MulFirstArgComma { .. } => 3,
+        ParserState::MulSecondArg { .. } => 4,
+    }
+}
+
+fn view(app: &App, model: &Model, frame: Frame) {
+    let Model { theme, font, .. } = model;
+    frame.clear(rgb_u32(theme.background));
+
+    // everything drawn depends only on the position in the timeline
+    if frame.nth() >= model.timeline.frames(model.fps) {
+        std::process::exit(0);
+    }
+    let t: f32 = frame.nth() as f32 / model.fps;
+    let cursor = model.timeline.at(t);
+    let current = cursor.event;
+
+    let draw = app.draw();
+    let win = app.window_rect();
+
+    let winp = win.pad(PADDING);
+
+    // the active token pulses as it's reached, while the highlight moves over
+    // from the previous step
+    let pulse = ease_out_cubic(progress(cursor.elapsed, PULSE));
+    let font_size = map_range(pulse, 0.0, 1.0, FONT_SIZE_MAX as f32, FONT_SIZE_MIN as f32);
+    let fade = ease_in_out_quad(progress(cursor.elapsed, TRANSITION));
+
+    let event = &model.trace.0[current];
+    let previous = current.checked_sub(1).map(|idx| &model.trace.0[idx]);
+    let active = active_span(event);
+    let previous_active = previous.map(active_span).unwrap_or(0..0);
+    let parser = match event {
+        TraceEvent::ParserEvent { state, .. } => Some((state, model.trace.emitted(current + 1))),
+        TraceEvent::TokenizerEvent { .. } => None,
+    };
+    let previous_state = match previous {
+        Some(TraceEvent::ParserEvent { state, .. }) => Some(state),
+        _ => None,
+    };
+
+    // search for the most recent message
+    let msg = model
+        .trace
+        .0
+        .iter()
+        .take(current + 1)
+        .rev()
+        .find_map(|trace| trace.evaluation());
+
+    // draw status pane
+    let status = Rect::from_w_h((BLOCK_SIZE + PADDING) * BLOCKS_PER_ROW as f32, BLOCK_SIZE)
+        .top_left_of(winp);
+    draw.rect()
+        .xy(status.xy())
+        .w_h((BLOCK_SIZE) * BLOCKS_PER_ROW as f32, BLOCK_SIZE)
+        .color(rgb_u32(theme.status));
+    if let Some(text) = msg {
+        draw.text(&text)
+            .font_size(40)
+            .font(font.clone())
+            .no_line_wrap()
+            .xy(status.xy())
+            .color(rgb_u32(theme.foreground));
+    }
+
+    // draw input blocks
+    let mut next_block = Rect::from_w_h(BLOCK_SIZE, BLOCK_SIZE)
+        .top_left_of(winp)
+        .shift_y(-PADDING - BLOCK_SIZE);
+    let mut row_start: Rect = next_block;
+    let mut col: i32 = 1;
+
+    // trace positions are byte offsets, and a character is lit if any of its
+    // bytes are
+    for (pos, character) in model.input.char_indices() {
+        let bytes = pos..pos + character.len_utf8();
+        let lit = |span: &Range<usize>| span.start < bytes.end && bytes.start < span.end;
+        let (highlight, font_size) = match (lit(&active), lit(&previous_active)) {
+            (true, true) => (1.0, font_size.round() as u32),
+            (true, false) => (fade, font_size.round() as u32),
+            (false, true) => (1.0 - fade, FONT_SIZE_MIN),
+            (false, false) => (0.0, FONT_SIZE_MIN),
+        };
+        let fg = mix(theme.foreground, theme.highlight_foreground, highlight);
+        let bg = mix(theme.background_dim, theme.highlight, highlight);
+        // draw block
+        draw.rect()
+            .xy(next_block.xy())
+            .wh(next_block.wh())
+            .color(bg);
+        draw.text(character.to_string().as_str())
+            .font_size(font_size)
+            .font(font.clone())
+            .xy(next_block.xy())
+            .color(fg);
+
+        // new row?
+        if col >= BLOCKS_PER_ROW {
+            next_block = row_start.below(row_start).shift_y(-PADDING);
+            col = 1;
+            row_start = next_block;
+        } else {
+            next_block = next_block.right_of(next_block).shift_x(PADDING);
+            col += 1;
+        }
+    }
+
+    // draw parser pane below the input
+    if let Some((state, instructions)) = parser {
+        let rows = model
+            .input
+            .chars()
+            .count()
+            .div_ceil(BLOCKS_PER_ROW as usize) as f32;
+        let pane = Rect::from_corners(
+            winp.bottom_left(),
+            pt2(
+                status.right(),
+                winp.top() - (rows + 1.0) * (BLOCK_SIZE + PADDING),
+            ),
+        );
+        let lit = Lit {
+            state,
+            previous: previous_state,
+            fade,
+        };
+        draw_parser(&draw, model, pane, lit, &instructions);
+    }
+
+    app.main_window()
+        .capture_frame(captured_frame_path(app, &frame));
+    draw.to_frame(app, &frame).unwrap();
+}
+
+/// The parser state to light up, moving over from the previous state.
+struct Lit<'a> {
+    state: &'a ParserState,
+    previous: Option<&'a ParserState>,
+    fade: f32,
+}
+
+impl Lit<'_> {
+    /// How lit the node at `idx` is, from `0.0` to `1.0`.
+    fn node(&self, idx: usize) -> f32 {
+        let was_lit = self.previous.map(state_node) == Some(idx);
+        match (idx == state_node(self.state), was_lit) {
+            (true, true) => 1.0,
+            (true, false) => self.fade,
+            (false, true) => 1.0 - self.fade,
+            (false, false) => 0.0,
+        }
+    }
+}
+
+/// Draw the parser state machine with the current state lit, followed by the
+/// enabled flag, the running total and the instructions emitted so far.
+fn draw_parser(draw: &Draw, model: &Model, pane: Rect, lit: Lit, instructions: &[&Mul]) {
+    let Model { theme, font, .. } = model;
+    let state = lit.state;
+    let node_count = STATE_NODES.len() as f32;
+    let node_width = (pane.w() - PADDING * (node_count - 1.0)) / node_count;
+    let node = |idx: usize| {
+        Rect::from_w_h(node_width, STATE_NODE_HEIGHT)
+            .top_left_of(pane)
+            .shift_x(idx as f32 * (node_width + PADDING))
+    };
+
+    // draw state machine nodes, with an edge to each following state
+    for (idx, label) in STATE_NODES.iter().enumerate() {
+        let rect = node(idx);
+        let fg = mix(theme.foreground, theme.highlight_foreground, lit.node(idx));
+        let bg = mix(theme.background_alt, theme.highlight, lit.node(idx));
+        draw.rect().xy(rect.xy()).wh(rect.wh()).color(bg);
+        draw.text(label)
+            .font_size(STATE_FONT_SIZE)
+            .font(font.clone())
+            .no_line_wrap()
+            .xy(rect.xy())
+            .color(fg);
+        if idx > 0 {
+            draw.arrow()
+                .start(node(idx - 1).mid_right())
+                .end(rect.mid_left())
+                .weight(2.0)
+                .color(rgb_u32(theme.foreground));
+        }
+    }
+
+    // a complete instruction returns to the empty state
+    let (first, last) = (node(0), node(STATE_NODES.len() - 1));
+    let return_y = first.bottom() - PADDING;
+    draw.polyline()
+        .weight(2.0)
+        .color(rgb_u32(theme.foreground))
+        .points([
+            last.mid_bottom(),
+            pt2(last.x(), return_y),
+            pt2(first.x(), return_y),
+        ]);
+    draw.arrow()
+        .start(pt2(first.x(), return_y))
+        .end(first.mid_bottom())
+        .weight(2.0)
+        .color(rgb_u32(theme.foreground));
+
+    // draw enabled flag and running total
+    let total: i64 = instructions.iter().map(|mul| mul.eval()).sum();
+    let summary = Rect::from_w_h(pane.w(), LINE_HEIGHT)
+        .below(first)
+        .align_left_of(pane)
+        .shift_y(-PADDING * 2.0);
+    let (flag, flag_color) = if state.enabled() {
+        ("enabled", theme.highlight)
+    } else {
+        ("disabled", theme.foreground)
+    };
+    draw.text(flag)
+        .font_size(STATE_FONT_SIZE)
+        .font(font.clone())
+        .xy(summary.xy())
+        .wh(summary.wh())
+        .left_justify()
+        .color(rgb_u32(flag_color));
+    draw.text(&format!("sum: {total}"))
+        .font_size(STATE_FONT_SIZE)
+        .font(font.clone())
+        .xy(summary.xy())
+        .wh(summary.wh())
+        .right_justify()
+        .color(rgb_u32(theme.foreground));
+
+    // draw the most recently emitted instructions that fit in the pane
+    let visible = ((summary.bottom() - pane.bottom()) / LINE_HEIGHT).max(0.0) as usize;
+    let skipped = instructions.len().saturating_sub(visible);
+    let mut line = summary;
+    for mul in instructions.iter().skip(skipped) {
+        line = line.below(line);
+        draw.text(&format!("{:?} = {}", mul, mul.eval()))
+            .font_size(STATE_FONT_SIZE)
+            .font(font.clone())
+            .xy(line.xy())
+            .wh(line.wh())
+            .left_justify()
+            .color(rgb_u32(theme.foreground));
+    }
+}
+
+fn captured_frame_path(app: &App, frame: &Frame) -> std::path::PathBuf {
+    // Create a path that we want to save this frame to.
+    app.project_path()
+        .expect("failed to locate `project_path`")
+        // Capture all frames to a directory called `/<path_to_nannou>/nannou/simple_capture`.
+        .join(app.exe_name().unwrap())
+        // Name each file after the number of the frame.
+        .join(format!("{:03}", frame.nth()))
+        // The extension will be PNG. We also support tiff, bmp, gif, jpeg, webp and some others.
+        .with_extension("png")
+}
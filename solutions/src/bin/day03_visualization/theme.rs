@@ -0,0 +1,64 @@
+use nannou::color::{rgb_u32, Rgb};
+use serde::Deserialize;
+use std::fs;
+
+/// Built-in themes, selectable by name with `--theme`.
+pub const THEMES: [(&str, &str); 2] = [
+    ("dark", include_str!("../../../themes/dark.toml")),
+    ("light", include_str!("../../../themes/light.toml")),
+];
+
+/// Colour palette, as `0xRRGGBB` values.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Theme {
+    /// Window background.
+    pub background: u32,
+    /// Background of inactive input blocks.
+    pub background_dim: u32,
+    /// Background of inactive state machine nodes.
+    pub background_alt: u32,
+    /// Background of the status pane.
+    pub status: u32,
+    /// Text, edges and the running total.
+    pub foreground: u32,
+    /// Background of the active block and state, and the enabled flag.
+    pub highlight: u32,
+    /// Text drawn on top of `highlight`.
+    pub highlight_foreground: u32,
+}
+
+impl Theme {
+    /// Load a built-in theme by name, or otherwise a palette file.
+    pub fn load(theme: &str) -> Result<Self, String> {
+        let palette = match THEMES.iter().find(|(name, _)| *name == theme) {
+            Some((_, palette)) => palette.to_string(),
+            None => fs::read_to_string(theme)
+                .map_err(|e| format!("failed to read theme {theme}: {e}"))?,
+        };
+        toml::from_str(&palette).map_err(|e| format!("failed to parse theme {theme}: {e}"))
+    }
+}
+
+/// Blend from colour `from` to colour `to`, with `amount` in `0.0..=1.0`.
+pub fn mix(from: u32, to: u32, amount: f32) -> Rgb<u8> {
+    let (from, to) = (rgb_u32(from), rgb_u32(to));
+    let channel = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * amount).round() as u8;
+    Rgb::new(
+        channel(from.red, to.red),
+        channel(from.green, to.green),
+        channel(from.blue, to.blue),
+    )
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_themes() {
+        for (name, _) in THEMES {
+            assert!(Theme::load(name).is_ok(), "failed to load theme {name}");
+        }
+    }
+}
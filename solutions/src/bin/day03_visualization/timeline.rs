@@ -0,0 +1,153 @@
+//! Maps time since the start of a capture onto trace events.
+//!
+//! Everything drawn is a function of the time `t` in seconds, and `t` is
+//! derived from the frame number and the capture frame rate, so a capture
+//! shows the same animation whatever rate it is recorded at.
+
+use advent_of_code_2024::day03::{Trace, TraceEvent};
+
+/// Seconds spent on an ordinary tokenizer or parser step.
+const STEP: f32 = 14.0 / 60.0;
+/// Seconds spent on a parser step that completes a `mul` instruction.
+const HOLD: f32 = 1.5;
+/// Seconds taken by highlights to move from one step to the next.
+pub const TRANSITION: f32 = 0.1;
+/// Seconds taken by the font pulse at the start of a step.
+pub const PULSE: f32 = 0.2;
+
+/// Start time of each event in a trace.
+#[derive(Debug)]
+pub struct Timeline {
+    starts: Vec<f32>,
+    duration: f32,
+}
+
+/// A point on a [`Timeline`].
+#[derive(Debug, PartialEq)]
+pub struct Cursor {
+    /// Index of the trace event on screen.
+    pub event: usize,
+    /// Seconds since that event started.
+    pub elapsed: f32,
+}
+
+impl Timeline {
+    pub fn new(trace: &Trace) -> Self {
+        let mut starts = Vec::with_capacity(trace.0.len());
+        let mut duration = 0.0;
+        for event in &trace.0 {
+            starts.push(duration);
+            duration += Self::event_duration(event);
+        }
+        Timeline { starts, duration }
+    }
+
+    fn event_duration(event: &TraceEvent) -> f32 {
+        match event {
+            TraceEvent::ParserEvent {
+                outcome: Some(_), ..
+            } => HOLD,
+            _ => STEP,
+        }
+    }
+
+    /// Total length of the timeline in seconds.
+    pub fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    /// Number of frames needed to capture the whole timeline at `fps`.
+    pub fn frames(&self, fps: f32) -> u64 {
+        (self.duration * fps).ceil() as u64
+    }
+
+    /// The event on screen `t` seconds in, holding the last event past the end.
+    pub fn at(&self, t: f32) -> Cursor {
+        let event = self
+            .starts
+            .partition_point(|start| *start <= t)
+            .saturating_sub(1);
+        Cursor {
+            event,
+            elapsed: t - self.starts[event],
+        }
+    }
+}
+
+/// Progress through an animation lasting `duration` seconds, `elapsed`
+/// seconds in, from `0.0` to `1.0`.
+pub fn progress(elapsed: f32, duration: f32) -> f32 {
+    (elapsed / duration).clamp(0.0, 1.0)
+}
+
+/// Starts fast and settles into the end value.
+pub fn ease_out_cubic(x: f32) -> f32 {
+    1.0 - (1.0 - x).powi(3)
+}
+
+/// Starts and ends slowly.
+pub fn ease_in_out_quad(x: f32) -> f32 {
+    if x < 0.5 {
+        2.0 * x * x
+    } else {
+        1.0 - (-2.0 * x + 2.0).powi(2) / 2.0
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use advent_of_code_2024::day03::{self, Instructions, ParserConfig};
+
+    #[test]
+    fn independent_of_frame_rate() {
+        let (_, trace) = Instructions::parse(&day03::example(2).unwrap(), ParserConfig::Part2);
+        let timeline = Timeline::new(&trace);
+        let rates = [24.0, 30.0, 60.0];
+        for fps in rates {
+            let captured = timeline.frames(fps) as f32 / fps;
+            assert!(captured >= timeline.duration() && captured < timeline.duration() + 1.0 / fps);
+        }
+
+        // every rate has a frame each sixth of a second, which should look the
+        // same at all of them
+        let sixths = (timeline.duration() * 6.0).ceil() as u64;
+        for sixth in 0..sixths {
+            let shown: Vec<_> = rates
+                .iter()
+                .map(|fps| {
+                    let frame = sixth * (*fps as u64) / 6;
+                    let cursor = timeline.at(frame as f32 / fps);
+                    let eased = [
+                        ease_out_cubic(progress(cursor.elapsed, TRANSITION)),
+                        ease_in_out_quad(progress(cursor.elapsed, PULSE)),
+                    ];
+                    (cursor.event, eased)
+                })
+                .collect();
+            for (event, eased) in &shown[1..] {
+                assert_eq!(*event, shown[0].0, "at {sixth} sixths of a second");
+                for (value, first) in eased.iter().zip(shown[0].1) {
+                    assert!(
+                        (value - first).abs() < 1e-4,
+                        "at {sixth} sixths of a second"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn holds_completed_instructions() {
+        let (_, trace) = Instructions::parse(&day03::example(2).unwrap(), ParserConfig::Part2);
+        let timeline = Timeline::new(&trace);
+        let holds = (0..trace.0.len())
+            .filter(|&event| Timeline::event_duration(&trace.0[event]) == HOLD)
+            .count();
+        assert_eq!(holds, 4);
+        assert_eq!(
+            timeline.at(timeline.duration() + 1.0).event,
+            trace.0.len() - 1
+        );
+    }
+}
@@ -0,0 +1,218 @@
+//! Day 1: Historian Hysteria.
+//!
+//! Compares lists of location IDs given as columns of the input.
+
+use crate::solution::Solution;
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+};
+
+pub mod fast;
+pub mod generate;
+
+/// Numbers from each whitespace separated column of the input.
+#[derive(Debug, PartialEq)]
+pub struct Columns(pub Vec<Vec<i64>>);
+
+/// Reason the input couldn't be read as columns. Lines and columns count
+/// from 1.
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    /// A line had a different number of columns to the first line.
+    ColumnCount {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A value wasn't a number.
+    Number {
+        line: usize,
+        column: usize,
+        value: String,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::ColumnCount {
+                line,
+                expected,
+                found,
+            } => write!(f, "line {line}: expected {expected} columns, found {found}"),
+            ParseError::Number {
+                line,
+                column,
+                value,
+            } => write!(f, "line {line}, column {column}: {value:?} is not a number"),
+        }
+    }
+}
+
+impl Columns {
+    /// Parse every column, collecting all errors rather than stopping at the
+    /// first. The first non-empty line decides how many columns there are.
+    pub fn parse(input: &str) -> Result<Self, Vec<ParseError>> {
+        let mut columns: Vec<Vec<i64>> = vec![];
+        let mut errors: Vec<ParseError> = vec![];
+        let lines = input
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty());
+
+        for (idx, line) in lines {
+            let values: Vec<&str> = line.split_whitespace().collect();
+            if columns.is_empty() {
+                columns = vec![vec![]; values.len()];
+            }
+            if values.len() != columns.len() {
+                errors.push(ParseError::ColumnCount {
+                    line: idx + 1,
+                    expected: columns.len(),
+                    found: values.len(),
+                });
+                continue;
+            }
+            for (column, value) in values.into_iter().enumerate() {
+                match value.parse::<i64>() {
+                    Ok(num) => columns[column].push(num),
+                    Err(_) => errors.push(ParseError::Number {
+                        line: idx + 1,
+                        column: column + 1,
+                        value: value.to_string(),
+                    }),
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(Columns(columns))
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// The column at `idx`, counting from 0.
+    pub fn get(&self, idx: usize) -> Result<&[i64], String> {
+        self.0
+            .get(idx)
+            .map(Vec::as_slice)
+            .ok_or_else(|| format!("no column {idx}, only {} columns", self.0.len()))
+    }
+}
+
+/// Total distance between the smallest numbers of each list, then the second
+/// smallest, and so on.
+pub fn distance(left: &[i64], right: &[i64]) -> i64 {
+    let (mut left, mut right) = (left.to_vec(), right.to_vec());
+    left.sort();
+    right.sort();
+
+    left.iter()
+        .zip(right.iter())
+        .map(|(left, right)| (left - right).abs())
+        .sum()
+}
+
+/// Sum of each number in `left` multiplied by how often it appears in
+/// `right`.
+pub fn similarity(left: &[i64], right: &[i64]) -> i64 {
+    let mut counts: HashMap<i64, i64> = HashMap::new();
+    right.iter().for_each(|num| {
+        counts
+            .entry(*num)
+            .and_modify(|count| *count += 1)
+            .or_insert(1);
+    });
+    left.iter()
+        .map(|search| {
+            let count = counts.get(search).unwrap_or(&0);
+            count * search
+        })
+        .sum()
+}
+
+/// The puzzle itself, comparing the first two columns.
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Historian Hysteria";
+    type Input = Columns;
+
+    fn parse(input: &str) -> Result<Columns, String> {
+        let columns = Columns::parse(input).map_err(|errors| {
+            let errors: Vec<String> = errors.iter().map(ParseError::to_string).collect();
+            errors.join("\n")
+        })?;
+        columns.get(1)?;
+        Ok(columns)
+    }
+
+    fn part1(input: &Columns) -> impl Display {
+        distance(&input.0[0], &input.0[1])
+    }
+
+    fn part2(input: &Columns) -> impl Display {
+        similarity(&input.0[0], &input.0[1])
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r#"
+3   4
+4   3
+2   5
+1   3
+3   9
+3   3
+"#;
+
+    #[test]
+    fn example() {
+        let columns = Columns::parse(EXAMPLE).unwrap();
+        let (left, right) = (columns.get(0).unwrap(), columns.get(1).unwrap());
+        assert_eq!(distance(left, right), 11);
+        assert_eq!(similarity(left, right), 31);
+    }
+
+    #[test]
+    fn columns() {
+        let columns = Columns::parse("1 2 3\n4 5 6\n").unwrap();
+        assert_eq!(columns, Columns(vec![vec![1, 4], vec![2, 5], vec![3, 6]]));
+        assert_eq!(
+            distance(columns.get(0).unwrap(), columns.get(2).unwrap()),
+            4
+        );
+        assert!(columns.get(3).is_err());
+    }
+
+    #[test]
+    fn parse_errors() {
+        let errors = Columns::parse("1 2 3\n4 x 6\n7 8\n9 10 y\n").unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                ParseError::Number {
+                    line: 2,
+                    column: 2,
+                    value: "x".to_string()
+                },
+                ParseError::ColumnCount {
+                    line: 3,
+                    expected: 3,
+                    found: 2
+                },
+                ParseError::Number {
+                    line: 4,
+                    column: 3,
+                    value: "y".to_string()
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,156 @@
+//! Parse-once implementation of day 1 for two columns of location IDs with
+//! at most five digits.
+//!
+//! Rather than collecting and sorting the lists, the input is parsed straight
+//! into a count of how often each ID appears in each list. Walking the counts
+//! in order of ID is a counting sort, which pairs off the lists for part 1,
+//! and part 2 multiplies the counts directly. Once [`Histograms`] has been
+//! allocated, nothing else is.
+
+/// Location IDs are below this bound.
+pub const ID_BOUND: usize = 100_000;
+
+/// How many times each location ID appears in each list.
+#[derive(Debug, Clone)]
+pub struct Histograms {
+    left: Box<[u32]>,
+    right: Box<[u32]>,
+}
+
+impl Default for Histograms {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Histograms {
+    pub fn new() -> Self {
+        Histograms {
+            left: vec![0; ID_BOUND].into_boxed_slice(),
+            right: vec![0; ID_BOUND].into_boxed_slice(),
+        }
+    }
+
+    /// Count the IDs in `input`, replacing any previous counts so the
+    /// histograms can be reused.
+    pub fn fill(&mut self, input: &[u8]) -> Result<(), String> {
+        self.left.fill(0);
+        self.right.fill(0);
+
+        let mut pos = 0;
+        let mut line = 1;
+        while pos < input.len() {
+            skip_spaces(input, &mut pos);
+            if pos < input.len() && input[pos] != b'\n' {
+                let left = number(input, &mut pos, line)?;
+                skip_spaces(input, &mut pos);
+                let right = number(input, &mut pos, line)?;
+                skip_spaces(input, &mut pos);
+                if pos < input.len() && input[pos] != b'\n' {
+                    return Err(format!("line {line}: expected 2 columns"));
+                }
+                self.left[left] += 1;
+                self.right[right] += 1;
+            }
+            pos += 1;
+            line += 1;
+        }
+        Ok(())
+    }
+
+    /// Total distance between the lists, pairing them off in sorted order.
+    pub fn distance(&self) -> i64 {
+        let mut total: i64 = 0;
+        let (mut left, mut right) = (0, 0);
+        let (mut left_count, mut right_count) = (self.left[0], self.right[0]);
+        loop {
+            while left_count == 0 {
+                left += 1;
+                if left == ID_BOUND {
+                    return total;
+                }
+                left_count = self.left[left];
+            }
+            while right_count == 0 {
+                right += 1;
+                if right == ID_BOUND {
+                    return total;
+                }
+                right_count = self.right[right];
+            }
+            let pairs = left_count.min(right_count);
+            total += pairs as i64 * (left as i64 - right as i64).abs();
+            left_count -= pairs;
+            right_count -= pairs;
+        }
+    }
+
+    /// Sum of each ID in the left list multiplied by how often it appears in
+    /// the right list.
+    pub fn similarity(&self) -> i64 {
+        self.left
+            .iter()
+            .zip(self.right.iter())
+            .enumerate()
+            .map(|(id, (left, right))| id as i64 * *left as i64 * *right as i64)
+            .sum()
+    }
+}
+
+fn skip_spaces(input: &[u8], pos: &mut usize) {
+    while *pos < input.len() && matches!(input[*pos], b' ' | b'\t' | b'\r') {
+        *pos += 1;
+    }
+}
+
+/// Read the ID starting at `pos`, leaving `pos` after it.
+fn number(input: &[u8], pos: &mut usize, line: usize) -> Result<usize, String> {
+    let start = *pos;
+    let mut value: usize = 0;
+    while let Some(digit @ b'0'..=b'9') = input.get(*pos) {
+        value = value * 10 + (digit - b'0') as usize;
+        if value >= ID_BOUND {
+            return Err(format!("line {line}: location ID out of range"));
+        }
+        *pos += 1;
+    }
+    if *pos == start {
+        return Err(format!("line {line}: expected a location ID"));
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::day01::{distance, similarity, Columns};
+
+    const EXAMPLE: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+    #[test]
+    fn example() {
+        let mut histograms = Histograms::new();
+        histograms.fill(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(histograms.distance(), 11);
+        assert_eq!(histograms.similarity(), 31);
+    }
+
+    #[test]
+    fn matches_columns() {
+        let input = "99999 0\r\n\n  12 12\n7 3";
+        let columns = Columns::parse(input).unwrap();
+        let (left, right) = (columns.get(0).unwrap(), columns.get(1).unwrap());
+        let mut histograms = Histograms::new();
+        histograms.fill(input.as_bytes()).unwrap();
+        assert_eq!(histograms.distance(), distance(left, right));
+        assert_eq!(histograms.similarity(), similarity(left, right));
+    }
+
+    #[test]
+    fn errors() {
+        let mut histograms = Histograms::new();
+        for input in ["1 2\n3\n", "1 2 3\n", "100000 1\n", "1 -2\n"] {
+            assert!(histograms.fill(input.as_bytes()).is_err(), "{input:?}");
+        }
+    }
+}
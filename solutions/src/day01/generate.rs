@@ -0,0 +1,80 @@
+//! Made up location ID lists shaped like the puzzle input: two columns of
+//! five digit IDs, with some of the right list's IDs also in the left list.
+
+use crate::rng::Rng;
+use clap::Args;
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Args)]
+pub struct Config {
+    /// Number of lines, each with an ID from both lists.
+    #[arg(long, default_value_t = Config::default().lines)]
+    pub lines: usize,
+    /// Chance of an ID in the right list being copied from the left list,
+    /// which is what part 2 counts.
+    #[arg(long, default_value_t = Config::default().overlap)]
+    pub overlap: f64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            lines: 1000,
+            overlap: 0.2,
+        }
+    }
+}
+
+pub fn generate(config: &Config, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let left: Vec<u64> = (0..config.lines)
+        .map(|_| rng.range(10_000..100_000))
+        .collect();
+    let mut input = String::with_capacity(config.lines * 14);
+    for id in &left {
+        let right = if rng.chance(config.overlap) {
+            *rng.choose(&left)
+        } else {
+            rng.range(10_000..100_000)
+        };
+        writeln!(input, "{id}   {right}").unwrap();
+    }
+    input
+}
+
+/// The default input, with `lines` lines.
+pub fn sized(seed: u64, lines: usize) -> String {
+    generate(
+        &Config {
+            lines,
+            ..Config::default()
+        },
+        seed,
+    )
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::day01::{fast::Histograms, similarity, Columns};
+
+    #[test]
+    fn shaped_like_input() {
+        let input = sized(1, 500);
+        assert_eq!(input, sized(1, 500));
+        assert_ne!(input, sized(2, 500));
+
+        let columns = Columns::parse(&input).unwrap();
+        let (left, right) = (columns.get(0).unwrap(), columns.get(1).unwrap());
+        assert_eq!(left.len(), 500);
+        assert!(left
+            .iter()
+            .chain(right)
+            .all(|id| (10_000..100_000).contains(id)));
+        assert!(similarity(left, right) > 0);
+
+        let mut histograms = Histograms::new();
+        histograms.fill(input.as_bytes()).unwrap();
+        assert_eq!(histograms.similarity(), similarity(left, right));
+    }
+}
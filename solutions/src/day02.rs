@@ -0,0 +1,518 @@
+//! Day 2: Red-Nosed Reports.
+//!
+//! Checks whether reports of levels change gradually in one direction, with
+//! the Problem Dampener allowed to remove some of the levels.
+
+use crate::{iter::IteratorExt, solution::Solution};
+use clap::ValueEnum;
+use std::{cmp::Ordering, fmt::Display, ops::RangeInclusive};
+
+pub mod generate;
+
+/// Which way levels may move through a report.
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Direction {
+    Increasing,
+    Decreasing,
+    Either,
+}
+
+impl Direction {
+    /// Whether levels may move from one to the next with this `ordering`.
+    pub fn allows(self, ordering: Ordering) -> bool {
+        match self {
+            Direction::Increasing => ordering == Ordering::Less,
+            Direction::Decreasing => ordering == Ordering::Greater,
+            Direction::Either => ordering != Ordering::Equal,
+        }
+    }
+}
+
+/// Rules a report must follow to be considered safe.
+#[derive(Debug, Clone)]
+pub struct SafetyPolicy {
+    /// Allowed differences between adjacent levels.
+    pub step: RangeInclusive<i64>,
+    /// Which way the levels must consistently move.
+    pub direction: Direction,
+    /// Whether adjacent levels may be equal, which doesn't count as a step in
+    /// any direction.
+    pub plateaus: bool,
+    /// How many levels the Problem Dampener may remove.
+    pub budget: usize,
+}
+
+impl SafetyPolicy {
+    pub const PART1: Self = Self {
+        step: 1..=3,
+        direction: Direction::Either,
+        plateaus: false,
+        budget: 0,
+    };
+
+    pub const PART2: Self = Self {
+        budget: 1,
+        ..Self::PART1
+    };
+
+    /// Whether level `b` may follow level `a` in a report moving with
+    /// `ordering`.
+    fn step(&self, a: i64, b: i64, ordering: Ordering) -> bool {
+        match a.cmp(&b) {
+            Ordering::Equal => self.plateaus,
+            cmp => cmp == ordering && self.step.contains(&(a - b).abs()),
+        }
+    }
+}
+
+/// Rule broken by a report, with the index of the first level of the
+/// offending adjacent pair.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Violation {
+    /// Fewer than two levels, so no direction at all.
+    TooShort,
+    /// Changed direction, or moved against the policy's direction.
+    Direction(usize),
+    /// Adjacent levels were equal.
+    Plateau(usize),
+    StepTooSmall(usize),
+    StepTooLarge(usize),
+}
+
+impl Violation {
+    pub fn rule(&self) -> &'static str {
+        match self {
+            Violation::TooShort => "too short",
+            Violation::Direction(_) => "direction",
+            Violation::Plateau(_) => "plateau",
+            Violation::StepTooSmall(_) => "step too small",
+            Violation::StepTooLarge(_) => "step too large",
+        }
+    }
+
+    /// Indices of the offending pair of levels.
+    pub fn pair(&self) -> Option<(usize, usize)> {
+        match self {
+            Violation::TooShort => None,
+            Violation::Direction(idx)
+            | Violation::Plateau(idx)
+            | Violation::StepTooSmall(idx)
+            | Violation::StepTooLarge(idx) => Some((*idx, idx + 1)),
+        }
+    }
+}
+
+/// Why a report was or wasn't safe.
+#[derive(Debug, PartialEq)]
+pub struct Explanation {
+    /// The first rule broken, if the report wasn't safe as it was.
+    pub violation: Option<Violation>,
+    /// Indices of the levels the dampener removed to make it safe.
+    pub removed: Option<Vec<usize>>,
+}
+
+impl Explanation {
+    pub const HEADER: [&str; 6] = ["report", "levels", "verdict", "rule", "pair", "removed"];
+
+    pub fn verdict(&self) -> &'static str {
+        match (&self.violation, &self.removed) {
+            (None, _) => "safe",
+            (Some(_), Some(_)) => "dampened",
+            (Some(_), None) => "unsafe",
+        }
+    }
+
+    /// Columns describing report number `report`, matching
+    /// [`Explanation::HEADER`].
+    pub fn row(&self, report: usize, levels: &Levels) -> [String; 6] {
+        let join = |values: Vec<String>| values.join(" ");
+        [
+            report.to_string(),
+            join(levels.0.iter().map(i64::to_string).collect()),
+            self.verdict().to_string(),
+            self.violation
+                .map(|violation| violation.rule().to_string())
+                .unwrap_or_default(),
+            self.violation
+                .and_then(|violation| violation.pair())
+                .map(|(a, b)| format!("{a}-{b}"))
+                .unwrap_or_default(),
+            self.removed
+                .as_ref()
+                .map(|removed| join(removed.iter().map(usize::to_string).collect()))
+                .unwrap_or_default(),
+        ]
+    }
+}
+
+#[derive(Debug)]
+pub struct Levels(pub Vec<i64>);
+
+impl Levels {
+    pub fn parse(line: &str) -> Self {
+        Self(
+            line.split_whitespace()
+                .filter_map(|level| level.parse::<i64>().ok())
+                .collect(),
+        )
+    }
+
+    fn monotonic(&self, policy: &SafetyPolicy) -> bool {
+        let mut ordering: Vec<Ordering> = self
+            .0
+            .iter()
+            .pairs()
+            .map(|(a, b)| a.cmp(b))
+            .filter(|ordering| !(policy.plateaus && ordering.is_eq()))
+            .collect();
+        ordering.dedup();
+        match ordering[..] {
+            // nothing but plateaus
+            [] => policy.plateaus && self.0.len() >= 2,
+            [ordering] => policy.direction.allows(ordering),
+            _ => false,
+        }
+    }
+
+    fn adjacency_check(&self, policy: &SafetyPolicy) -> bool {
+        self.0
+            .iter()
+            .pairs()
+            .map(|(a, b)| (a - b).abs())
+            .all(|d| (d == 0 && policy.plateaus) || policy.step.contains(&d))
+    }
+
+    pub fn safe(&self, policy: &SafetyPolicy) -> bool {
+        self.monotonic(policy) && self.adjacency_check(policy)
+    }
+
+    #[cfg(test)]
+    fn remove_index(&self, removed_index: usize) -> Self {
+        let levels = self
+            .0
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, lvl)| {
+                if idx == removed_index {
+                    None
+                } else {
+                    Some(*lvl)
+                }
+            })
+            .collect();
+        Self(levels)
+    }
+
+    /// Find the fewest levels, at most the policy's budget, that can be
+    /// removed to make the report safe, returning their indices in ascending
+    /// order. Where there's a choice, earlier levels are removed.
+    pub fn dampen(&self, policy: &SafetyPolicy) -> Option<Vec<usize>> {
+        [Ordering::Less, Ordering::Greater]
+            .into_iter()
+            .filter(|ordering| policy.direction.allows(*ordering))
+            .filter_map(|ordering| self.dampen_towards(ordering, policy))
+            .min_by_key(Vec::len)
+    }
+
+    /// Like [`Levels::dampen`], for reports moving with `ordering`.
+    ///
+    /// Looks for the cheapest chain of kept levels where each is a safe step
+    /// from the last. Chains skipping more than `budget` levels at once are
+    /// never cheap enough, so each level only looks back `budget + 1` levels:
+    /// a single pass taking `O(n * budget)`, rather than trying every
+    /// combination of removals.
+    fn dampen_towards(&self, ordering: Ordering, policy: &SafetyPolicy) -> Option<Vec<usize>> {
+        let levels = &self.0;
+        let len = levels.len();
+        let budget = policy.budget;
+
+        // removals needed for a chain of at least two levels ending at each
+        // index, counting levels skipped before the chain
+        let mut cost: Vec<Option<usize>> = vec![None; len];
+        // the level kept before each index, and whether the chain continues
+        // before that
+        let mut prev: Vec<(usize, bool)> = vec![(0, false); len];
+        for idx in 0..len {
+            for before in idx.saturating_sub(budget + 1)..idx {
+                if !policy.step(levels[before], levels[idx], ordering) {
+                    continue;
+                }
+                // either extend the chain ending at `before`, or start a new one
+                let (removals, extends) = match cost[before] {
+                    Some(removals) if removals < before => (removals, true),
+                    _ => (before, false),
+                };
+                let removals = removals + (idx - before - 1);
+                // on ties, prefer removing earlier levels
+                if cost[idx].is_none_or(|cost| removals <= cost) {
+                    cost[idx] = Some(removals);
+                    prev[idx] = (before, extends);
+                }
+            }
+        }
+
+        // cheapest chain, counting the levels skipped after it
+        let (mut idx, removals) = cost
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, cost)| cost.map(|cost| (idx, cost + len - 1 - idx)))
+            .min_by_key(|(_, removals)| *removals)?;
+        if removals > budget {
+            return None;
+        }
+        let mut kept = vec![false; len];
+        kept[idx] = true;
+        loop {
+            let (before, extends) = prev[idx];
+            kept[before] = true;
+            if !extends {
+                break;
+            }
+            idx = before;
+        }
+        Some((0..len).filter(|idx| !kept[*idx]).collect())
+    }
+
+    pub fn dampened_safe(&self, policy: &SafetyPolicy) -> bool {
+        self.safe(policy) || self.dampen(policy).is_some()
+    }
+
+    /// The first adjacent pair of levels breaking `policy`, if any.
+    pub fn violation(&self, policy: &SafetyPolicy) -> Option<Violation> {
+        if self.0.len() < 2 {
+            return Some(Violation::TooShort);
+        }
+        let mut direction: Option<Ordering> = None;
+        for (idx, [a, b]) in self.0.iter().copied().indexed_windows() {
+            let ordering = a.cmp(&b);
+            if ordering.is_eq() {
+                if policy.plateaus {
+                    continue;
+                }
+                return Some(Violation::Plateau(idx));
+            }
+            if !policy.direction.allows(ordering) || direction.is_some_and(|d| d != ordering) {
+                return Some(Violation::Direction(idx));
+            }
+            direction = Some(ordering);
+            let step = (a - b).abs();
+            if step < *policy.step.start() {
+                return Some(Violation::StepTooSmall(idx));
+            }
+            if step > *policy.step.end() {
+                return Some(Violation::StepTooLarge(idx));
+            }
+        }
+        None
+    }
+
+    pub fn explain(&self, policy: &SafetyPolicy) -> Explanation {
+        let violation = self.violation(policy);
+        let removed = violation.and_then(|_| self.dampen(policy));
+        Explanation { violation, removed }
+    }
+}
+
+#[derive(Debug)]
+pub struct Reports(pub Vec<Levels>);
+
+impl Reports {
+    pub fn parse(input: &str) -> Self {
+        Self(input.lines().map(Levels::parse).collect())
+    }
+
+    /// Explain every report's classification under `policy`, as a header
+    /// row followed by a row per report.
+    pub fn explain(&self, policy: &SafetyPolicy) -> Vec<[String; 6]> {
+        let header = Explanation::HEADER.map(String::from);
+        let rows = self
+            .0
+            .iter()
+            .enumerate()
+            .map(|(idx, levels)| levels.explain(policy).row(idx + 1, levels));
+        std::iter::once(header).chain(rows).collect()
+    }
+
+    /// Number of reports that are safe under `policy`.
+    pub fn count_safe(&self, policy: &SafetyPolicy) -> usize {
+        self.0
+            .iter()
+            .filter(|levels| levels.dampened_safe(policy))
+            .count()
+    }
+}
+
+/// The puzzle itself, counting reports safe under each part's policy.
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Red-Nosed Reports";
+    type Input = Reports;
+
+    fn parse(input: &str) -> Result<Reports, String> {
+        Ok(Reports::parse(input))
+    }
+
+    fn part1(input: &Reports) -> impl Display {
+        input.count_safe(&SafetyPolicy::PART1)
+    }
+
+    fn part2(input: &Reports) -> impl Display {
+        input.count_safe(&SafetyPolicy::PART2)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = r#"
+7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9
+"#;
+
+    #[test]
+    fn example_part1() {
+        let safety: Vec<bool> = Reports::parse(EXAMPLE.trim())
+            .0
+            .iter()
+            .map(|l| l.safe(&SafetyPolicy::PART1))
+            .collect();
+        assert_eq!(safety, vec![true, false, false, false, false, true]);
+    }
+
+    #[test]
+    fn example_explanations() {
+        let reports = Reports::parse(EXAMPLE.trim());
+        let rows: Vec<String> = reports
+            .explain(&SafetyPolicy::PART2)
+            .iter()
+            .map(|row| row.join(" | ").trim_end().to_string())
+            .collect();
+        insta::assert_snapshot!(rows.join("\n"));
+    }
+
+    #[test]
+    fn example_part2() {
+        let dampened: Vec<Option<Vec<usize>>> = Reports::parse(EXAMPLE.trim())
+            .0
+            .iter()
+            .map(|l| l.dampen(&SafetyPolicy::PART2))
+            .collect();
+        assert_eq!(
+            dampened,
+            vec![
+                Some(vec![]),
+                None,
+                None,
+                Some(vec![1]),
+                Some(vec![2]),
+                Some(vec![])
+            ]
+        );
+    }
+
+    #[test]
+    fn custom_policy() {
+        let policy = SafetyPolicy {
+            step: 1..=5,
+            direction: Direction::Increasing,
+            plateaus: true,
+            budget: 0,
+        };
+        let safety: Vec<bool> = ["1 2 2 7 8", "1 1 1", "8 7 6", "1 2 9", "5"]
+            .into_iter()
+            .map(|line| Levels::parse(line).safe(&policy))
+            .collect();
+        assert_eq!(safety, vec![true, true, false, false, false]);
+    }
+
+    #[test]
+    fn example_explain() {
+        let explanations: Vec<Explanation> = Reports::parse(EXAMPLE.trim())
+            .0
+            .iter()
+            .map(|l| l.explain(&SafetyPolicy::PART2))
+            .collect();
+        let explanation = |violation, removed| Explanation { violation, removed };
+        assert_eq!(
+            explanations,
+            vec![
+                explanation(None, None),
+                explanation(Some(Violation::StepTooLarge(1)), None),
+                explanation(Some(Violation::StepTooLarge(2)), None),
+                explanation(Some(Violation::Direction(1)), Some(vec![1])),
+                explanation(Some(Violation::Plateau(2)), Some(vec![2])),
+                explanation(None, None),
+            ]
+        );
+        assert_eq!(
+            explanations[3].row(4, &Levels::parse("1 3 2 4 5")),
+            ["4", "1 3 2 4 5", "dampened", "direction", "1-2", "1"].map(String::from)
+        );
+    }
+
+    /// Fewest removals, at most the policy's budget, that make `levels` safe,
+    /// by trying every combination.
+    fn brute_force(levels: &Levels, policy: &SafetyPolicy) -> Option<usize> {
+        if levels.safe(policy) {
+            return Some(0);
+        }
+        if policy.budget == 0 {
+            return None;
+        }
+        let policy = SafetyPolicy {
+            budget: policy.budget - 1,
+            ..policy.clone()
+        };
+        (0..levels.0.len())
+            .filter_map(|idx| brute_force(&levels.remove_index(idx), &policy))
+            .min()
+            .map(|removals| removals + 1)
+    }
+
+    fn policies() -> impl Strategy<Value = SafetyPolicy> {
+        let direction = prop_oneof![
+            Just(Direction::Increasing),
+            Just(Direction::Decreasing),
+            Just(Direction::Either),
+        ];
+        (0i64..3, 0i64..4, direction, any::<bool>(), 0usize..4).prop_map(
+            |(min_step, extra, direction, plateaus, budget)| SafetyPolicy {
+                step: min_step..=min_step + extra,
+                direction,
+                plateaus,
+                budget,
+            },
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn dampen_matches_brute_force(
+            levels in prop::collection::vec(0i64..12, 0..10),
+            policy in policies(),
+        ) {
+            let levels = Levels(levels);
+            prop_assert_eq!(levels.violation(&policy).is_none(), levels.safe(&policy));
+            let removed = levels.dampen(&policy);
+            prop_assert_eq!(removed.as_ref().map(Vec::len), brute_force(&levels, &policy));
+            if let Some(removed) = removed {
+                let kept = levels
+                    .0
+                    .iter()
+                    .enumerate()
+                    .filter(|(idx, _)| !removed.contains(idx))
+                    .map(|(_, level)| *level)
+                    .collect();
+                prop_assert!(Levels(kept).safe(&policy));
+            }
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! Made up reports shaped like the puzzle input, with a chosen share of them
+//! safe under part 1's policy. Unsafe reports are safe ones with a single
+//! fault added, so some of them can still be dampened.
+
+use crate::rng::Rng;
+use clap::Args;
+
+#[derive(Debug, Clone, Args)]
+pub struct Config {
+    #[arg(long, default_value_t = Config::default().reports)]
+    pub reports: usize,
+    /// Fewest levels in a report, at least 3.
+    #[arg(long, default_value_t = Config::default().min_levels)]
+    pub min_levels: usize,
+    #[arg(long, default_value_t = Config::default().max_levels)]
+    pub max_levels: usize,
+    /// Chance of a report being safe under part 1's policy.
+    #[arg(long, default_value_t = Config::default().safe)]
+    pub safe: f64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            reports: 1000,
+            min_levels: 5,
+            max_levels: 8,
+            safe: 0.5,
+        }
+    }
+}
+
+/// Ways to make a safe report unsafe, between two adjacent levels.
+#[derive(Debug, Clone, Copy)]
+enum Fault {
+    Jump,
+    Plateau,
+    Reversal,
+}
+
+/// Increasing levels with steps of 1 to 3, and a fault after level `idx`.
+fn report(rng: &mut Rng, len: usize, fault: Option<(Fault, usize)>) -> Vec<i64> {
+    let mut level = rng.range(1..50) as i64;
+    let mut levels = vec![level];
+    for idx in 0..len - 1 {
+        let step = match fault {
+            Some((Fault::Jump, at)) if at == idx => rng.range(4..10) as i64,
+            Some((Fault::Plateau, at)) if at == idx => 0,
+            Some((Fault::Reversal, at)) if at == idx => -(rng.range(1..4) as i64),
+            _ => rng.range(1..4) as i64,
+        };
+        level += step;
+        levels.push(level);
+    }
+    // a reversal early on could take levels below 1
+    let lowest = *levels.iter().min().unwrap();
+    levels
+        .iter()
+        .map(|level| level - lowest.min(1) + 1)
+        .collect()
+}
+
+pub fn generate(config: &Config, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let min = config.min_levels.max(3);
+    let max = config.max_levels.max(min);
+    let mut input = String::new();
+    for _ in 0..config.reports {
+        let len = rng.range(min as u64..max as u64 + 1) as usize;
+        let fault = (!rng.chance(config.safe)).then(|| {
+            let fault = *rng.choose(&[Fault::Jump, Fault::Plateau, Fault::Reversal]);
+            (fault, rng.index(len - 1))
+        });
+        let mut levels = report(&mut rng, len, fault);
+        if rng.chance(0.5) {
+            levels.reverse();
+        }
+        let levels: Vec<String> = levels.iter().map(i64::to_string).collect();
+        input += &levels.join(" ");
+        input.push('\n');
+    }
+    input
+}
+
+/// The default input, with `reports` reports.
+pub fn sized(seed: u64, reports: usize) -> String {
+    generate(
+        &Config {
+            reports,
+            ..Config::default()
+        },
+        seed,
+    )
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::day02::{Reports, SafetyPolicy};
+
+    fn safe(config: &Config, seed: u64) -> usize {
+        Reports::parse(&generate(config, seed)).count_safe(&SafetyPolicy::PART1)
+    }
+
+    #[test]
+    fn share_of_safe_reports() {
+        assert_eq!(sized(1, 100), sized(1, 100));
+        let config = |safe| Config {
+            reports: 1000,
+            safe,
+            ..Config::default()
+        };
+        assert_eq!(safe(&config(1.0), 1), 1000);
+        assert_eq!(safe(&config(0.0), 1), 0);
+        assert!((400..600).contains(&safe(&config(0.5), 1)));
+
+        let reports = Reports::parse(&sized(2, 1000));
+        let dampened = reports.count_safe(&SafetyPolicy::PART2);
+        assert!(dampened > reports.count_safe(&SafetyPolicy::PART1));
+        assert!(dampened < 1000);
+    }
+}
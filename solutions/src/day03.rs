@@ -0,0 +1,646 @@
+//! Day 3: Mull It Over.
+//!
+//! Tokenizes corrupted memory and parses the `mul` instructions out of it,
+//! optionally recording a trace of every step for visualizing.
+
+use crate::solution::{Day, Solution};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::Display,
+    fs::{self, File},
+    io::{BufRead, BufReader, Write},
+    path::Path,
+};
+
+pub mod generate;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Trace(pub Vec<TraceEvent>);
+
+/// The first line of a JSON Lines trace, so it can be replayed without
+/// having to know what was traced.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Header {
+    input: String,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub enum TraceEvent {
+    TokenizerEvent {
+        pos: usize,
+        tokens: Vec<Token>,
+        evaluation: Option<String>,
+    },
+    ParserEvent {
+        pos: usize,
+        token: Token,
+        state: ParserState,
+        /// The instruction completed by this token, if any.
+        outcome: Option<Outcome>,
+        evaluation: Option<String>,
+    },
+}
+
+/// What became of a completed instruction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Outcome {
+    /// Counted towards the sum.
+    Emitted(Mul),
+    /// Left out because instructions were disabled.
+    Skipped(Mul),
+}
+
+impl Trace {
+    /// Write the trace of `input` as JSON Lines: a header with the input,
+    /// then one event per line.
+    pub fn write_jsonl(&self, input: &str, mut writer: impl Write) -> Result<(), String> {
+        let header = Header {
+            input: input.to_string(),
+        };
+        serde_json::to_writer(&mut writer, &header).map_err(|e| e.to_string())?;
+        writeln!(writer).map_err(|e| e.to_string())?;
+        for event in &self.0 {
+            serde_json::to_writer(&mut writer, event).map_err(|e| e.to_string())?;
+            writeln!(writer).map_err(|e| e.to_string())?;
+        }
+        writer.flush().map_err(|e| e.to_string())
+    }
+
+    /// The instructions emitted by the first `events` events, in order.
+    pub fn emitted(&self, events: usize) -> Vec<&Mul> {
+        self.0
+            .iter()
+            .take(events)
+            .filter_map(|event| match event {
+                TraceEvent::ParserEvent {
+                    outcome: Some(Outcome::Emitted(mul)),
+                    ..
+                } => Some(mul),
+                TraceEvent::ParserEvent { .. } => None,
+                TraceEvent::TokenizerEvent { .. } => None,
+            })
+            .collect()
+    }
+
+    /// Read a trace written by [`Trace::write_jsonl`] along with the input it
+    /// traces, skipping blank lines.
+    pub fn read_jsonl(reader: impl BufRead) -> Result<(String, Self), String> {
+        let mut header: Option<Header> = None;
+        let mut events = vec![];
+        for (idx, line) in reader.lines().enumerate() {
+            let line = line.map_err(|e| e.to_string())?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let parsed = match header {
+                None => serde_json::from_str(&line).map(|read| header = Some(read)),
+                Some(_) => serde_json::from_str(&line).map(|event| events.push(event)),
+            };
+            parsed.map_err(|e| format!("line {}: {e}", idx + 1))?;
+        }
+        let header = header.ok_or("the trace is empty")?;
+        Ok((header.input, Trace(events)))
+    }
+}
+
+impl TraceEvent {
+    pub fn evaluation(&self) -> Option<String> {
+        match self {
+            Self::TokenizerEvent { evaluation, .. } => evaluation.clone(),
+            Self::ParserEvent { evaluation, .. } => evaluation.clone(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Mul(i64, i64);
+
+#[derive(Debug)]
+pub struct Instructions(pub Vec<Mul>);
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum Token {
+    Enable,
+    Disable,
+    MulStart,
+    Number(String),
+    Comma,
+    MulEnd,
+    Garbage,
+}
+
+#[derive(Debug)]
+pub struct Parser(pub Vec<Token>);
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum ParserState {
+    Empty { enabled: bool },
+    MulStart { enabled: bool },
+    MulFirstArg { enabled: bool, arg1: i64 },
+    MulFirstArgComma { enabled: bool, arg1: i64 },
+    MulSecondArg { enabled: bool, arg1: i64, arg2: i64 },
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ParserConfig {
+    Part1,
+    Part2,
+}
+
+#[derive(Debug)]
+pub struct Tokenizer(pub String);
+
+impl ParserState {
+    pub fn enabled(&self) -> bool {
+        match self {
+            ParserState::Empty { enabled } => *enabled,
+            ParserState::MulStart { enabled } => *enabled,
+            ParserState::MulFirstArg { enabled, .. } => *enabled,
+            ParserState::MulFirstArgComma { enabled, .. } => *enabled,
+            ParserState::MulSecondArg { enabled, .. } => *enabled,
+        }
+    }
+}
+
+impl Token {
+    /// Number of input characters this token was read from.
+    pub fn source_len(&self) -> usize {
+        match self {
+            Token::Enable => 4,
+            Token::Disable => 7,
+            Token::MulStart => 4,
+            Token::Number(num) => num.len(),
+            Token::Comma | Token::MulEnd | Token::Garbage => 1,
+        }
+    }
+}
+
+impl Parser {
+    /// Advance the parser state by one token, or `None` if the token is not
+    /// accepted in the current state.
+    fn transition(state: &ParserState, token: &Token) -> Option<ParserState> {
+        let enabled = state.enabled();
+        match (token, state) {
+            (Token::Enable, _) => Some(ParserState::Empty { enabled: true }),
+            (Token::Disable, _) => Some(ParserState::Empty { enabled: false }),
+            (Token::MulStart, ParserState::Empty { .. }) => Some(ParserState::MulStart { enabled }),
+            (Token::Number(num), ParserState::MulStart { .. }) => Some(ParserState::MulFirstArg {
+                enabled,
+                arg1: num.parse::<i64>().unwrap(),
+            }),
+            (Token::Number(num), ParserState::MulFirstArgComma { arg1, .. }) => {
+                Some(ParserState::MulSecondArg {
+                    enabled,
+                    arg1: *arg1,
+                    arg2: num.parse::<i64>().unwrap(),
+                })
+            }
+            (Token::Comma, ParserState::MulFirstArg { arg1, .. }) => {
+                Some(ParserState::MulFirstArgComma {
+                    enabled,
+                    arg1: *arg1,
+                })
+            }
+            (Token::MulEnd, ParserState::MulSecondArg { .. }) => {
+                Some(ParserState::Empty { enabled })
+            }
+            _ => None,
+        }
+    }
+
+    pub fn parse(&self, config: ParserConfig) -> (Instructions, Trace) {
+        let mut trace: Vec<TraceEvent> = vec![];
+        let instructions = self.run(config, Some(&mut trace));
+        (instructions, Trace(trace))
+    }
+
+    /// Parse without recording a trace.
+    pub fn instructions(&self, config: ParserConfig) -> Instructions {
+        self.run(config, None)
+    }
+
+    fn run(&self, config: ParserConfig, mut trace: Option<&mut Vec<TraceEvent>>) -> Instructions {
+        let mut instructions: Vec<Mul> = vec![];
+        let mut state = ParserState::Empty { enabled: true };
+        let mut pos: usize = 0;
+
+        for token in &self.0 {
+            let (next, evaluation) = match Self::transition(&state, token) {
+                Some(next) => (next, Some(format!("accepted: {:?}", token))),
+                None => (
+                    ParserState::Empty {
+                        enabled: state.enabled(),
+                    },
+                    // garbage between instructions isn't worth reporting
+                    (!matches!(state, ParserState::Empty { .. }))
+                        .then(|| format!("rejected: {:?}", token)),
+                ),
+            };
+            let mut outcome = None;
+            let evaluation = match (token, &state) {
+                (
+                    Token::MulEnd,
+                    ParserState::MulSecondArg {
+                        enabled,
+                        arg1,
+                        arg2,
+                    },
+                ) => {
+                    let mul = Mul(*arg1, *arg2);
+                    if *enabled || config == ParserConfig::Part1 {
+                        let evaluation = Some(format!("emitted: {:?}", mul));
+                        instructions.push(mul.clone());
+                        outcome = Some(Outcome::Emitted(mul));
+                        evaluation
+                    } else {
+                        let evaluation = Some(format!("skipped: {:?}", mul));
+                        outcome = Some(Outcome::Skipped(mul));
+                        evaluation
+                    }
+                }
+                _ => evaluation,
+            };
+            if let Some(trace) = trace.as_deref_mut() {
+                trace.push(TraceEvent::ParserEvent {
+                    pos,
+                    token: token.clone(),
+                    state: next.clone(),
+                    outcome,
+                    evaluation,
+                });
+            }
+            pos += token.source_len();
+            state = next;
+        }
+
+        Instructions(instructions)
+    }
+}
+
+impl Tokenizer {
+    fn enable(&self, pos: usize) -> Option<(Token, usize)> {
+        let rest = self.0.get(pos..)?;
+        rest.starts_with("do()").then(|| (Token::Enable, pos + 4))
+    }
+
+    fn disable(&self, pos: usize) -> Option<(Token, usize)> {
+        let rest = self.0.get(pos..)?;
+        rest.starts_with("don't()")
+            .then(|| (Token::Disable, pos + 7))
+    }
+
+    fn mul_start(&self, pos: usize) -> Option<(Token, usize)> {
+        let rest = self.0.get(pos..)?;
+        rest.starts_with("mul(").then(|| (Token::MulStart, pos + 4))
+    }
+
+    fn mul_end(&self, pos: usize) -> Option<(Token, usize)> {
+        let rest = self.0.get(pos..)?;
+        rest.starts_with(")").then(|| (Token::MulEnd, pos + 1))
+    }
+
+    /// One to three ASCII digits, as many as an argument can have, so a
+    /// longer run splits into numbers the parser rejects.
+    fn number(&self, pos: usize) -> Option<(Token, usize)> {
+        let rest = self.0.get(pos..)?;
+        let digits = rest.bytes().take(3).take_while(u8::is_ascii_digit).count();
+        (digits > 0).then(|| (Token::Number(rest[..digits].to_string()), pos + digits))
+    }
+
+    fn comma(&self, pos: usize) -> Option<(Token, usize)> {
+        let rest = self.0.get(pos..)?;
+        rest.starts_with(",").then(|| (Token::Comma, pos + 1))
+    }
+
+    fn garbage(&self, pos: usize) -> Option<(Token, usize)> {
+        Some((Token::Garbage, pos + 1))
+    }
+
+    pub fn tokenize(&self) -> (Vec<Token>, Trace) {
+        let mut trace: Vec<TraceEvent> = vec![];
+        let tokens = self.run(Some(&mut trace));
+        (tokens, Trace(trace))
+    }
+
+    /// Tokenize without recording a trace, which grows quadratically with the
+    /// input.
+    pub fn tokens(&self) -> Vec<Token> {
+        self.run(None)
+    }
+
+    fn run(&self, mut trace: Option<&mut Vec<TraceEvent>>) -> Vec<Token> {
+        let tokenizers = [
+            Self::enable,
+            Self::disable,
+            Self::mul_start,
+            Self::mul_end,
+            Self::number,
+            Self::comma,
+            Self::garbage,
+        ];
+
+        let mut pos: usize = 0;
+        let mut tokens: Vec<Token> = vec![];
+
+        while pos <= self.0.len() {
+            if let Some(trace) = trace.as_deref_mut() {
+                trace.push(TraceEvent::TokenizerEvent {
+                    pos,
+                    tokens: tokens.clone(),
+                    evaluation: None,
+                });
+            }
+            if let Some((token, new_pos)) = tokenizers.iter().find_map(|t| t(self, pos)) {
+                if let Some(trace) = trace.as_deref_mut() {
+                    trace.push(TraceEvent::TokenizerEvent {
+                        pos,
+                        tokens: tokens.clone(),
+                        evaluation: Some(format!("found token: {:?}", token)),
+                    });
+                }
+                tokens.push(token);
+                pos = new_pos;
+            } else {
+                panic!("failed to match any tokenizers")
+            }
+        }
+        tokens
+    }
+}
+
+impl Mul {
+    pub fn eval(&self) -> i64 {
+        self.0 * self.1
+    }
+}
+
+impl Instructions {
+    pub fn eval(&self) -> i64 {
+        self.0.iter().map(|mul| mul.eval()).sum()
+    }
+
+    /// Tokenize and parse `input`, returning the trace of both passes.
+    pub fn parse(input: &str, config: ParserConfig) -> (Self, Trace) {
+        let (tokens, Trace(mut trace)) = Tokenizer(input.to_string()).tokenize();
+        let (instructions, Trace(parser_trace)) = Parser(tokens).parse(config);
+        trace.extend(parser_trace);
+        (instructions, Trace(trace))
+    }
+}
+
+/// The puzzle itself, parsing the tokens once for both parts.
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Mull It Over";
+    type Input = Parser;
+
+    fn parse(input: &str) -> Result<Parser, String> {
+        Ok(Parser(Tokenizer(input.to_string()).tokens()))
+    }
+
+    fn part1(input: &Parser) -> impl Display {
+        input.instructions(ParserConfig::Part1).eval()
+    }
+
+    fn part2(input: &Parser) -> impl Display {
+        input.instructions(ParserConfig::Part2).eval()
+    }
+}
+
+/// The memory in the puzzle description's example for `part`, from the
+/// examples saved by `fetch`.
+pub fn example(part: u8) -> Result<String, String> {
+    let day = Day::new::<Puzzle>();
+    day.examples()?
+        .into_iter()
+        .find(|example| match part {
+            1 => example.part1.is_some(),
+            _ => example.part2.is_some(),
+        })
+        .map(|example| example.input.trim().to_string())
+        .ok_or_else(|| {
+            let path = day.examples_path().unwrap_or_default();
+            format!("{} has no example for part {part}", path.display())
+        })
+}
+
+/// The input and trace replayed from the JSON Lines `trace`, or otherwise
+/// the memory at `input`, or the example for `part`, traced with `part`'s
+/// rules.
+pub fn load_trace(
+    trace: Option<&Path>,
+    input: Option<&Path>,
+    part: u8,
+) -> Result<(String, Trace), String> {
+    if let Some(path) = trace {
+        let file =
+            File::open(path).map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+        return Trace::read_jsonl(BufReader::new(file))
+            .map_err(|e| format!("failed to parse {}: {e}", path.display()));
+    }
+    let input = match input {
+        Some(path) => fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?,
+        None => example(part)?,
+    };
+    let input = input.trim().to_string();
+    let config = match part {
+        1 => ParserConfig::Part1,
+        _ => ParserConfig::Part2,
+    };
+    let (_, trace) = Instructions::parse(&input, config);
+    Ok((input, trace))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn example_part1() {
+        let memory = example(1).unwrap();
+        let (instructions, _) = Instructions::parse(&memory, ParserConfig::Part1);
+        assert_eq!(
+            instructions.0,
+            vec![Mul(2, 4), Mul(5, 5), Mul(11, 8), Mul(8, 5)]
+        );
+        assert_eq!(instructions.eval(), 161);
+    }
+
+    #[test]
+    fn example_part2() {
+        let memory = example(2).unwrap();
+        let (instructions, _) = Instructions::parse(&memory, ParserConfig::Part2);
+        assert_eq!(instructions.0, vec![Mul(2, 4), Mul(8, 5)]);
+        assert_eq!(instructions.eval(), 48);
+    }
+
+    #[test]
+    fn example_part2_parser_trace() {
+        let memory = example(2).unwrap();
+        let (_, trace) = Instructions::parse(&memory, ParserConfig::Part2);
+        let emitted: Vec<(usize, String)> = trace
+            .0
+            .iter()
+            .filter_map(|event| match event {
+                TraceEvent::ParserEvent {
+                    pos,
+                    evaluation: Some(evaluation),
+                    ..
+                } if !evaluation.starts_with("accepted") && !evaluation.starts_with("rejected") => {
+                    Some((*pos, evaluation.clone()))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            emitted,
+            vec![
+                (8, "emitted: Mul(2, 4)".to_string()),
+                (35, "skipped: Mul(5, 5)".to_string()),
+                (56, "skipped: Mul(11, 8)".to_string()),
+                (71, "emitted: Mul(8, 5)".to_string()),
+            ]
+        );
+        let outcomes: Vec<(usize, &Outcome)> = trace
+            .0
+            .iter()
+            .filter_map(|event| match event {
+                TraceEvent::ParserEvent {
+                    pos,
+                    outcome: Some(outcome),
+                    ..
+                } => Some((*pos, outcome)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                (8, &Outcome::Emitted(Mul(2, 4))),
+                (35, &Outcome::Skipped(Mul(5, 5))),
+                (56, &Outcome::Skipped(Mul(11, 8))),
+                (71, &Outcome::Emitted(Mul(8, 5))),
+            ]
+        );
+        assert_eq!(trace.emitted(trace.0.len()), vec![&Mul(2, 4), &Mul(8, 5)]);
+        match trace.0.last() {
+            Some(TraceEvent::ParserEvent { state, .. }) => assert!(state.enabled()),
+            event => panic!("expected a parser event, got {:?}", event),
+        }
+    }
+
+    /// One line per event that did something: where it happened, the
+    /// parser's state and what came of it.
+    fn compact(trace: &Trace) -> String {
+        let lines: Vec<String> = trace
+            .0
+            .iter()
+            .filter_map(|event| {
+                let evaluation = event.evaluation()?;
+                Some(match event {
+                    TraceEvent::TokenizerEvent { pos, .. } => {
+                        format!("tokenize {pos:>2}: {evaluation}")
+                    }
+                    TraceEvent::ParserEvent { pos, state, .. } => {
+                        format!("parse    {pos:>2}: {state:?}: {evaluation}")
+                    }
+                })
+            })
+            .collect();
+        lines.join("\n")
+    }
+
+    #[test]
+    fn example_tokens() {
+        insta::assert_debug_snapshot!(Tokenizer(example(1).unwrap()).tokens());
+    }
+
+    #[test]
+    fn example_part2_trace() {
+        let (_, trace) = Instructions::parse(&example(2).unwrap(), ParserConfig::Part2);
+        insta::assert_snapshot!(compact(&trace));
+    }
+
+    #[test]
+    fn puzzle() {
+        let input = Puzzle::parse(&example(1).unwrap()).unwrap();
+        assert_eq!(Puzzle::part1(&input).to_string(), "161");
+        let input = Puzzle::parse(&example(2).unwrap()).unwrap();
+        assert_eq!(Puzzle::part2(&input).to_string(), "48");
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(
+            Tokenizer("1234".to_string()).tokens(),
+            vec![
+                Token::Number("123".to_string()),
+                Token::Number("4".to_string()),
+                Token::Garbage
+            ]
+        );
+        // none of these have arguments of one to three ASCII digits
+        let memory = "mul(٣,4)mul(1234,5)mul(99999999999999999999,1)mul(1,²)mul(123,4)";
+        let (instructions, _) = Instructions::parse(memory, ParserConfig::Part1);
+        assert_eq!(instructions.0, vec![Mul(123, 4)]);
+    }
+
+    #[test]
+    fn trace_jsonl_roundtrip() {
+        let input = example(2).unwrap();
+        let (_, trace) = Instructions::parse(&input, ParserConfig::Part2);
+        let mut jsonl: Vec<u8> = vec![];
+        trace.write_jsonl(&input, &mut jsonl).unwrap();
+        assert_eq!(
+            jsonl.iter().filter(|b| **b == b'\n').count(),
+            trace.0.len() + 1
+        );
+        assert_eq!(Trace::read_jsonl(&jsonl[..]).unwrap(), (input, trace));
+        assert!(Trace::read_jsonl(&b"{}\n"[..])
+            .unwrap_err()
+            .starts_with("line 1:"));
+        assert!(Trace::read_jsonl(&b"{\"input\":\"x\"}\n{}\n"[..])
+            .unwrap_err()
+            .starts_with("line 2:"));
+        assert_eq!(
+            Trace::read_jsonl(&b"\n"[..]).unwrap_err(),
+            "the trace is empty"
+        );
+    }
+
+    #[test]
+    fn loading_traces() {
+        let dir = std::env::temp_dir().join(format!("day03-traces-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let (input, trace) = load_trace(None, None, 2).unwrap();
+        assert_eq!(input, example(2).unwrap());
+
+        // written through a buffer that's dropped without being flushed
+        let path = dir.join("trace.jsonl");
+        let file = File::create(&path).unwrap();
+        trace
+            .write_jsonl(&input, std::io::BufWriter::new(file))
+            .unwrap();
+        assert_eq!(
+            load_trace(Some(&path), None, 1).unwrap(),
+            (
+                input,
+                Instructions::parse(&example(2).unwrap(), ParserConfig::Part2).1
+            )
+        );
+
+        let path = dir.join("memory.txt");
+        fs::write(&path, "do()mul(2,3)\n").unwrap();
+        let (input, trace) = load_trace(None, Some(&path), 1).unwrap();
+        assert_eq!(input, "do()mul(2,3)");
+        assert_eq!(trace.emitted(trace.0.len()).len(), 1);
+        assert!(load_trace(Some(&dir.join("missing.jsonl")), None, 2)
+            .unwrap_err()
+            .starts_with("failed to read"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,121 @@
+//! Made up corrupted memory shaped like the puzzle input: `mul` instructions,
+//! `do()` and `don't()` among garbage, along with near misses that mustn't be
+//! parsed as instructions.
+
+use crate::rng::Rng;
+use clap::Args;
+
+#[derive(Debug, Clone, Args)]
+pub struct Config {
+    /// Length of the memory in characters, give or take an instruction.
+    #[arg(long, default_value_t = Config::default().length)]
+    pub length: usize,
+    /// Chance of each instruction or character of garbage being a valid
+    /// `mul(a,b)`.
+    #[arg(long, default_value_t = Config::default().valid)]
+    pub valid: f64,
+    /// Chance of it being something that almost looks like an instruction.
+    #[arg(long, default_value_t = Config::default().near_miss)]
+    pub near_miss: f64,
+    /// Chance of it being a `do()` or `don't()`.
+    #[arg(long, default_value_t = Config::default().toggles)]
+    pub toggles: f64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            length: 20_000,
+            valid: 0.03,
+            near_miss: 0.02,
+            toggles: 0.005,
+        }
+    }
+}
+
+/// Characters that can't form any part of an instruction on their own,
+/// since there's no `m`, `d` or digit.
+const GARBAGE: &[u8] = b"!@#$%^&*()[]{}<>'+-,:;?/ selectwhyhowwhatwhere";
+
+/// Memory and the answers to both parts for it.
+fn generate_with_answers(config: &Config, seed: u64) -> (String, [i64; 2]) {
+    let mut rng = Rng::new(seed);
+    let mut memory = String::with_capacity(config.length + 16);
+    let mut answers = [0, 0];
+    let mut enabled = true;
+    while memory.len() < config.length {
+        let mut number = || rng.range(1..1000);
+        let (a, b) = (number(), number());
+        let roll = rng.next_u64() as f64 / u64::MAX as f64;
+        if roll < config.valid {
+            memory += &format!("mul({a},{b})");
+            answers[0] += (a * b) as i64;
+            if enabled {
+                answers[1] += (a * b) as i64;
+            }
+        } else if roll < config.valid + config.near_miss {
+            let near_misses = [
+                format!("mul({a},{b}]"),
+                format!("mul[{a},{b})"),
+                format!("mul({a}, {b})"),
+                format!("mul ({a},{b})"),
+                format!("mul({a}*{b})"),
+                format!("mul(,{b})"),
+                "do[]".to_string(),
+                "don't ()".to_string(),
+                "dont()".to_string(),
+            ];
+            let near_miss: &String = rng.choose(&near_misses);
+            memory += near_miss;
+        } else if roll < config.valid + config.near_miss + config.toggles {
+            enabled = rng.chance(0.5);
+            memory += if enabled { "do()" } else { "don't()" };
+        } else {
+            memory.push(*rng.choose(GARBAGE) as char);
+        }
+    }
+    (memory, answers)
+}
+
+pub fn generate(config: &Config, seed: u64) -> String {
+    generate_with_answers(config, seed).0
+}
+
+/// The default input, `length` characters long.
+pub fn sized(seed: u64, length: usize) -> String {
+    generate(
+        &Config {
+            length,
+            ..Config::default()
+        },
+        seed,
+    )
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{day03::Puzzle, solution::Solution};
+
+    #[test]
+    fn answers_match() {
+        assert_eq!(sized(1, 1000), sized(1, 1000));
+        let configs = [
+            Config::default(),
+            Config {
+                valid: 0.3,
+                near_miss: 0.3,
+                toggles: 0.1,
+                ..Config::default()
+            },
+        ];
+        for (seed, config) in configs.iter().enumerate() {
+            let (memory, [part1, part2]) = generate_with_answers(config, seed as u64);
+            assert!(memory.len() >= config.length);
+            let input = Puzzle::parse(&memory).unwrap();
+            assert_eq!(Puzzle::part1(&input).to_string(), part1.to_string());
+            assert_eq!(Puzzle::part2(&input).to_string(), part2.to_string());
+            assert!(part2 > 0 && part2 < part1);
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! Windowing adapters for iterators that work on the stable toolchain, in
+//! place of `Iterator::map_windows`.
+
+use std::iter::Enumerate;
+
+/// Extra adapters available on every iterator.
+pub trait IteratorExt: Iterator + Sized {
+    /// Each item paired with the one after it: `[a, b, c]` yields `(a, b)`
+    /// then `(b, c)`.
+    fn pairs(self) -> Pairs<Self>
+    where
+        Self::Item: Clone,
+    {
+        Pairs {
+            iter: self,
+            prev: None,
+        }
+    }
+
+    /// Overlapping windows of `N` consecutive items: `[a, b, c, d]` yields
+    /// `[a, b, c]` then `[b, c, d]`. Yields nothing if there are fewer than
+    /// `N` items.
+    ///
+    /// # Panics
+    ///
+    /// If `N` is zero.
+    fn windows<const N: usize>(self) -> Windows<Self, N>
+    where
+        Self::Item: Clone,
+    {
+        assert!(N > 0, "window size must be non-zero");
+        Windows {
+            iter: self,
+            window: None,
+        }
+    }
+
+    /// Like [`IteratorExt::windows`], with the index of the first item in
+    /// each window.
+    fn indexed_windows<const N: usize>(self) -> Enumerate<Windows<Self, N>>
+    where
+        Self::Item: Clone,
+    {
+        self.windows().enumerate()
+    }
+}
+
+impl<I: Iterator> IteratorExt for I {}
+
+/// Iterator returned by [`IteratorExt::pairs`].
+#[derive(Debug, Clone)]
+pub struct Pairs<I: Iterator> {
+    iter: I,
+    prev: Option<I::Item>,
+}
+
+impl<I> Iterator for Pairs<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = (I::Item, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let prev = match self.prev.take() {
+            Some(prev) => prev,
+            None => self.iter.next()?,
+        };
+        let next = self.iter.next()?;
+        self.prev = Some(next.clone());
+        Some((prev, next))
+    }
+}
+
+/// Iterator returned by [`IteratorExt::windows`].
+#[derive(Debug, Clone)]
+pub struct Windows<I: Iterator, const N: usize> {
+    iter: I,
+    window: Option<[I::Item; N]>,
+}
+
+impl<I, const N: usize> Iterator for Windows<I, N>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = [I::Item; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.window {
+            Some(window) => {
+                window.rotate_left(1);
+                window[N - 1] = self.iter.next()?;
+            }
+            None => {
+                let first: Vec<I::Item> = self.iter.by_ref().take(N).collect();
+                self.window = Some(first.try_into().ok()?);
+            }
+        }
+        self.window.clone()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn pairs() {
+        let pairs: Vec<(i64, i64)> = [1, 2, 3].into_iter().pairs().collect();
+        assert_eq!(pairs, vec![(1, 2), (2, 3)]);
+        assert_eq!([1].into_iter().pairs().next(), None);
+    }
+
+    #[test]
+    fn windows() {
+        let windows: Vec<[char; 3]> = "abcd".chars().windows().collect();
+        assert_eq!(windows, vec![['a', 'b', 'c'], ['b', 'c', 'd']]);
+        assert_eq!("ab".chars().windows::<3>().next(), None);
+    }
+
+    #[test]
+    fn indexed_windows() {
+        let windows: Vec<(usize, [i64; 2])> = [5, 6, 7].into_iter().indexed_windows().collect();
+        assert_eq!(windows, vec![(0, [5, 6]), (1, [6, 7])]);
+    }
+}
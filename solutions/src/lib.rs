@@ -0,0 +1,13 @@
+//! Code shared between solutions.
+
+pub mod bench;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod iter;
+pub mod progress;
+pub mod rng;
+pub mod runner;
+pub mod scaffold;
+pub mod settings;
+pub mod solution;
@@ -0,0 +1,236 @@
+//! The progress table in the README: the stars earned each day, as saved by
+//! `fetch progress`, with links to each day's solution and description and
+//! how long it takes to run. Descriptions and progress are found where
+//! `aoc.toml` has `fetch` save them.
+
+use crate::{
+    runner::format_duration,
+    settings::Settings,
+    solution::{self, Day},
+};
+use serde::Deserialize;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+pub const START: &str = "<!-- progress -->";
+pub const END: &str = "<!-- /progress -->";
+
+#[derive(Debug, Deserialize)]
+pub struct Stars {
+    pub day: u8,
+    pub stars: u8,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Progress {
+    pub year: Option<u16>,
+    #[serde(default)]
+    pub day: Vec<Stars>,
+}
+
+impl Progress {
+    /// The progress saved at `path`, or none if it hasn't been.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Progress::default());
+        }
+        let toml = fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+        Self::parse(&toml).map_err(|e| format!("failed to parse {}: {e}", path.display()))
+    }
+
+    fn parse(toml: &str) -> Result<Self, String> {
+        let progress: Progress = toml::from_str(toml).map_err(|e| e.to_string())?;
+        match progress.day.iter().find(|stars| stars.stars > 2) {
+            Some(stars) => Err(format!(
+                "day {} has {} stars, but there are only 2 a day",
+                stars.day, stars.stars
+            )),
+            None => Ok(progress),
+        }
+    }
+
+    pub fn stars(&self, day: u8) -> Option<u8> {
+        self.day
+            .iter()
+            .find(|stars| stars.day == day)
+            .map(|stars| stars.stars)
+    }
+}
+
+/// A day in the table.
+#[derive(Debug)]
+pub struct Row {
+    pub day: u8,
+    pub title: Option<&'static str>,
+    /// Stars earned, if the day has unlocked.
+    pub stars: Option<u8>,
+    /// Paths from the workspace root.
+    pub solution: Option<String>,
+    pub description: Option<String>,
+    pub runtime: Option<Duration>,
+}
+
+/// A row for every day that's unlocked or solved, timing each solved day
+/// with `measure`.
+pub fn rows(
+    progress: &Progress,
+    settings: &Settings,
+    workspace: &Path,
+    measure: impl Fn(&Day) -> Option<Duration>,
+) -> Vec<Row> {
+    let existing = |path: PathBuf| {
+        let path = path.strip_prefix(workspace).unwrap_or(&path);
+        workspace
+            .join(path)
+            .exists()
+            .then(|| path.display().to_string())
+    };
+    let solutions = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    (1..=25)
+        .filter_map(|day| {
+            let solved = solution::find(day).ok();
+            let stars = progress.stars(day);
+            if solved.is_none() && stars.is_none() {
+                return None;
+            }
+            Some(Row {
+                day,
+                title: solved.map(|solved| solved.title),
+                stars,
+                solution: existing(solutions.join(format!("day{day:02}.rs"))),
+                description: existing(settings.description_path(day)),
+                runtime: solved.and_then(&measure),
+            })
+        })
+        .collect()
+}
+
+/// The rows as a Markdown table, with each title linking to its puzzle.
+pub fn table(rows: &[Row], year: u16) -> String {
+    let link = |path: &Option<String>| match path {
+        Some(path) => {
+            let name = Path::new(path).file_name().unwrap_or_default();
+            format!("[{}]({path})", name.to_string_lossy())
+        }
+        None => String::new(),
+    };
+    let mut table = "| day | puzzle | stars | solution | description | runtime |\n\
+                     | --: | ------ | ----- | -------- | ----------- | ------: |\n"
+        .to_string();
+    for row in rows {
+        let title = row
+            .title
+            .map_or_else(|| format!("day {}", row.day), String::from);
+        let puzzle = format!("[{title}](https://adventofcode.com/{year}/day/{})", row.day);
+        let stars = match row.stars {
+            Some(stars) => {
+                let stars = usize::from(stars.min(2));
+                "★".repeat(stars) + &"☆".repeat(2 - stars)
+            }
+            None => String::new(),
+        };
+        table += &format!(
+            "| {} | {puzzle} | {stars} | {} | {} | {} |\n",
+            row.day,
+            link(&row.solution),
+            link(&row.description),
+            row.runtime.map(format_duration).unwrap_or_default()
+        );
+    }
+    table
+}
+
+/// `readme` with the table between the progress markers replaced.
+pub fn replace(readme: &str, table: &str) -> Result<String, String> {
+    let missing = || format!("the README has no `{START}` and `{END}` around a table");
+    let start = readme.find(START).ok_or_else(missing)? + START.len();
+    let end = start + readme[start..].find(END).ok_or_else(missing)?;
+    Ok(format!("{}\n{table}{}", &readme[..start], &readme[end..]))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn tables() {
+        let rows = [
+            Row {
+                day: 1,
+                title: Some("Historian Hysteria"),
+                stars: Some(2),
+                solution: Some("solutions/src/day01.rs".to_string()),
+                description: Some("solutions/src/bin/day01.md".to_string()),
+                runtime: Some(Duration::from_micros(1250)),
+            },
+            Row {
+                day: 2,
+                title: None,
+                stars: Some(1),
+                solution: None,
+                description: Some("solutions/src/bin/day02.md".to_string()),
+                runtime: None,
+            },
+        ];
+        // only made by hand, since loading rejects it
+        let too_many = Row {
+            day: 3,
+            title: None,
+            stars: Some(3),
+            solution: None,
+            description: None,
+            runtime: None,
+        };
+        assert!(table(&[too_many], 2024).contains("| ★★ |"));
+        let table = table(&rows, 2024);
+        insta::assert_snapshot!(table, @r"
+        | day | puzzle | stars | solution | description | runtime |
+        | --: | ------ | ----- | -------- | ----------- | ------: |
+        | 1 | [Historian Hysteria](https://adventofcode.com/2024/day/1) | ★★ | [day01.rs](solutions/src/day01.rs) | [day01.md](solutions/src/bin/day01.md) | 1.25ms |
+        | 2 | [day 2](https://adventofcode.com/2024/day/2) | ★☆ |  | [day02.md](solutions/src/bin/day02.md) |  |
+        ");
+
+        let readme = format!("# aoc\n\n{START}\nold\n{END}\n\nmore\n");
+        assert_eq!(
+            replace(&readme, &table).unwrap(),
+            format!("# aoc\n\n{START}\n{table}{END}\n\nmore\n")
+        );
+        assert!(replace("# aoc\n", &table).is_err());
+    }
+
+    #[test]
+    fn settings() {
+        let workspace = std::env::temp_dir().join(format!("aoc-progress-{}", std::process::id()));
+        fs::create_dir_all(workspace.join("docs/2023")).unwrap();
+
+        fs::write(
+            workspace.join("aoc.toml"),
+            "year = 2023\ndescriptions = \"docs\"\ndescription_file = \"{year}/{day}.md\"\n\
+             request_interval = 10\n",
+        )
+        .unwrap();
+        fs::write(workspace.join("docs/2023/1.md"), "").unwrap();
+        let mut settings = Settings::load(&workspace).unwrap();
+        settings.year = 2023;
+        assert_eq!(settings.progress, Path::new("progress.toml"));
+        let rows = rows(&Progress::default(), &settings, &workspace, |_| None);
+        assert_eq!(rows[0].description.as_deref(), Some("docs/2023/1.md"));
+        assert_eq!(rows[1].description, None);
+        fs::remove_dir_all(workspace).unwrap();
+    }
+
+    #[test]
+    fn progress() {
+        let progress = Progress::parse("year = 2024\n\n[[day]]\nday = 4\nstars = 1\n").unwrap();
+        assert_eq!(progress.stars(4), Some(1));
+        assert_eq!(progress.stars(5), None);
+        assert_eq!(
+            Progress::parse("[[day]]\nday = 4\nstars = 3\n").unwrap_err(),
+            "day 4 has 3 stars, but there are only 2 a day"
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//! Printing answers along with how long each phase of getting them took, so
+//! a slow day shows up without running a full benchmark.
+
+use crate::bench::{format_nanos, Phase};
+use std::time::Duration;
+
+/// Answers to a day's puzzle, and the wall time of each phase.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub day: u8,
+    pub title: &'static str,
+    pub answers: [String; 2],
+    pub timings: Vec<(Phase, Duration)>,
+}
+
+impl Report {
+    pub fn total(&self) -> Duration {
+        self.timings.iter().map(|(_, time)| *time).sum()
+    }
+
+    fn timing(&self, phase: Phase) -> Option<Duration> {
+        self.timings
+            .iter()
+            .find(|(p, _)| *p == phase)
+            .map(|(_, time)| *time)
+    }
+
+    /// Answers followed by the time of each phase.
+    pub fn print(&self) {
+        println!("part 1: {}", self.answers[0]);
+        println!("part 2: {}", self.answers[1]);
+        let timings: Vec<String> = self
+            .timings
+            .iter()
+            .map(|(phase, time)| format!("{phase} {}", format_duration(*time)))
+            .collect();
+        println!(
+            "time: {} (total {})",
+            timings.join(", "),
+            format_duration(self.total())
+        );
+    }
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    format_nanos(duration.as_nanos() as f64)
+}
+
+/// A row per day with the time of each phase, flagging days that took longer
+/// than `budget`, then the total across days.
+pub fn totals(reports: &[Report], budget: Duration) -> Vec<String> {
+    let phases = [Phase::Read, Phase::Parse, Phase::Part1, Phase::Part2];
+    let row = |day: String, times: Vec<String>, total: String, note: &str| {
+        let times: Vec<String> = times.iter().map(|time| format!("{time:>11}")).collect();
+        format!("{day:<5} {} {total:>11}  {note}", times.join(" "))
+            .trim_end()
+            .to_string()
+    };
+
+    let header: Vec<String> = phases.iter().map(Phase::to_string).collect();
+    let mut rows = vec![row("day".into(), header, "total".into(), "")];
+    for report in reports {
+        let times = phases
+            .iter()
+            .map(|phase| {
+                report
+                    .timing(*phase)
+                    .map(format_duration)
+                    .unwrap_or_default()
+            })
+            .collect();
+        let over = report.total() > budget;
+        rows.push(row(
+            report.day.to_string(),
+            times,
+            format_duration(report.total()),
+            if over { "over budget" } else { "" },
+        ));
+    }
+    let sums = phases
+        .iter()
+        .map(|phase| {
+            let sum: Duration = reports.iter().filter_map(|r| r.timing(*phase)).sum();
+            format_duration(sum)
+        })
+        .collect();
+    let total: Duration = reports.iter().map(Report::total).sum();
+    rows.push(row("all".into(), sums, format_duration(total), ""));
+    let over = reports.iter().filter(|r| r.total() > budget).count();
+    rows.push(format!(
+        "{over} of {} days over the {} budget",
+        reports.len(),
+        format_duration(budget)
+    ));
+    rows
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn report(day: u8, micros: [u64; 4]) -> Report {
+        let phases = [Phase::Read, Phase::Parse, Phase::Part1, Phase::Part2];
+        Report {
+            day,
+            title: "",
+            answers: ["1".into(), "2".into()],
+            timings: phases
+                .into_iter()
+                .zip(micros.map(Duration::from_micros))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn flags_days_over_budget() {
+        let reports = [report(1, [10, 20, 30, 40]), report(2, [1, 2, 300, 1000])];
+        assert_eq!(reports[1].total(), Duration::from_micros(1303));
+        let rows = totals(&reports, Duration::from_millis(1));
+        assert_eq!(rows.len(), 5);
+        assert!(rows[1].ends_with("100.00µs"));
+        assert!(rows[2].ends_with("1.30ms  over budget"));
+        assert!(rows[3].starts_with("all") && rows[3].ends_with("1.40ms"));
+        assert_eq!(rows[4], "1 of 2 days over the 1.00ms budget");
+    }
+}
@@ -0,0 +1,395 @@
+//! Starting a new day: a solution module and binary from templates,
+//! registered so the runner, benchmarks and example tests pick it up.
+
+use crate::settings::Settings;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+const MODULE: &str = r#"//! Day {day}: {title}.
+
+use crate::solution::Solution;
+use std::fmt::Display;
+
+/// The puzzle itself.
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = {day};
+    const TITLE: &'static str = {title:?};
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Vec<String>, String> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part1(_input: &Vec<String>) -> impl Display {
+        "unsolved"
+    }
+
+    fn part2(_input: &Vec<String>) -> impl Display {
+        "unsolved"
+    }
+}
+"#;
+
+const BIN: &str = r#"use advent_of_code_2024::{day{nn}::Puzzle, solution::Day};
+
+fn main() -> Result<(), String> {
+    Day::new::<Puzzle>().run(None)?.print();
+    Ok(())
+}
+"#;
+
+const EXAMPLES: &str = r#"# Examples from the puzzle description, checked by `cargo test`.
+#
+# [[example]]
+# input = """
+# """
+# part1 = ""
+# part2 = ""
+"#;
+
+/// `template` for `day`, with `{title:?}` standing for the title as a string
+/// literal.
+fn fill(template: &str, day: u8, title: &str) -> String {
+    template
+        .replace("{day}", &day.to_string())
+        .replace("{nn}", &format!("{day:02}"))
+        .replace("{title:?}", &format!("{title:?}"))
+        .replace("{title}", title)
+}
+
+/// `lib` with a module declared for `day`, keeping the declarations sorted.
+fn register_module(lib: &str, day: u8) -> Result<String, String> {
+    let declaration = format!("pub mod day{day:02};");
+    let mut lines: Vec<&str> = lib.lines().collect();
+    if lines.contains(&declaration.as_str()) {
+        return Err(format!("day {day} is already declared in lib.rs"));
+    }
+    let at = lines
+        .iter()
+        .position(|line| line.starts_with("pub mod ") && *line > declaration.as_str())
+        .or_else(|| {
+            lines
+                .iter()
+                .rposition(|line| line.starts_with("pub mod "))
+                .map(|idx| idx + 1)
+        })
+        .ok_or("lib.rs declares no modules")?;
+    lines.insert(at, &declaration);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// The entries of the import list `imports`, split at the commas outside
+/// braces, with any nested list put on one line.
+fn import_entries(imports: &str) -> Vec<String> {
+    let mut entries = vec![];
+    let (mut depth, mut start) = (0, 0);
+    for (idx, c) in imports.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            ',' if depth == 0 => {
+                entries.push(&imports[start..idx]);
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    entries.push(&imports[start..]);
+    entries
+        .into_iter()
+        .map(|entry| {
+            let entry = entry.split_whitespace().collect::<Vec<_>>().join(" ");
+            entry
+                .replace("{ ", "{")
+                .replace(" }", "}")
+                .replace(",}", "}")
+        })
+        .filter(|entry| !entry.is_empty())
+        .collect()
+}
+
+/// `entries` one to a line the way rustfmt lays them out, with consecutive
+/// days sharing a line.
+fn format_imports(entries: &[String]) -> String {
+    let is_day = |entry: &String| {
+        entry
+            .strip_prefix("day")
+            .is_some_and(|nn| nn.len() == 2 && nn.bytes().all(|b| b.is_ascii_digit()))
+    };
+    let mut lines: Vec<String> = vec![];
+    for (idx, entry) in entries.iter().enumerate() {
+        match lines.last_mut() {
+            Some(line) if is_day(entry) && idx > 0 && is_day(&entries[idx - 1]) => {
+                *line += &format!(" {entry},");
+            }
+            _ => lines.push(format!("    {entry},")),
+        }
+    }
+    lines.join("\n")
+}
+
+/// `solution` with `day` imported and added to `DAYS`, in order.
+fn register_day(solution: &str, day: u8) -> Result<String, String> {
+    let name = format!("day{day:02}");
+    let opening = "use crate::{";
+    let start = solution
+        .find(opening)
+        .ok_or("solution.rs has no imports from the crate")?
+        + opening.len();
+    let end = start
+        + solution[start..]
+            .find("};")
+            .ok_or("the imports from the crate aren't closed")?;
+    let mut entries = import_entries(&solution[start..end]);
+    let at = entries
+        .iter()
+        .position(|entry| *entry > name)
+        .unwrap_or(entries.len());
+    entries.insert(at, name.clone());
+    let mut solution = format!(
+        "{}\n{}\n{}",
+        &solution[..start],
+        format_imports(&entries),
+        &solution[end..]
+    );
+
+    let days = solution
+        .find("pub const DAYS: &[Day] = &[")
+        .ok_or("solution.rs has no DAYS")?;
+    let end = days + solution[days..].find("];").ok_or("DAYS isn't closed")?;
+    let entry = format!("    Day::new::<{name}::Puzzle>(),\n");
+    let at = solution[days..end]
+        .lines()
+        .scan(days, |offset, line| {
+            let start = *offset;
+            *offset += line.len() + 1;
+            Some((start, line))
+        })
+        .find(|(_, line)| {
+            line.trim_start()
+                .strip_prefix("Day::new::<day")
+                .is_some_and(|rest| rest[..2] > name[3..])
+        })
+        .map_or(end, |(start, _)| start);
+    solution.insert_str(at, &entry);
+    Ok(solution)
+}
+
+/// The title in a description saved by `fetch`.
+fn description_title(description: &str) -> Option<String> {
+    let heading = description
+        .lines()
+        .find(|line| line.starts_with("## --- Day"))?;
+    let (_, title) = heading.split_once(": ")?;
+    Some(title.trim_end_matches(" ---").to_string())
+}
+
+/// Fetch the description and input of `day` with the `fetch` binary, run
+/// from the workspace the crate at `root` is in.
+pub fn fetch(root: &Path, day: u8) -> Result<(), String> {
+    let workspace = root.parent().ok_or("the crate isn't in a workspace")?;
+    let status = Command::new(env!("CARGO"))
+        .args(["run", "--quiet", "--bin", "fetch", "--", &day.to_string()])
+        .current_dir(workspace)
+        .status()
+        .map_err(|e| format!("failed to run fetch: {e}"))?;
+    if !status.success() {
+        return Err(format!("fetch failed: {status}"));
+    }
+    Ok(())
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("failed to read {}: {e}", path.display()))
+}
+
+/// Check day `day` can be created in the crate at `root`, without any of
+/// its files already existing.
+pub fn check(root: &Path, day: u8) -> Result<(), String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("there is no day {day}"));
+    }
+    let module = root.join(format!("src/day{day:02}.rs"));
+    let bin = root.join(format!("src/bin/day{day:02}.rs"));
+    if let Some(existing) = [&module, &bin].into_iter().find(|path| path.exists()) {
+        return Err(format!("{} already exists", existing.display()));
+    }
+    register_module(&read(&root.join("src/lib.rs"))?, day)?;
+    Ok(())
+}
+
+/// Create day `day` in the crate at `root`, titled `title` or otherwise with
+/// the title from its description if it's been fetched. The examples and
+/// description are where the workspace's `aoc.toml` has them. Returns the
+/// files created or changed. Nothing is written if any of the files to create
+/// already exist.
+pub fn scaffold(root: &Path, day: u8, title: Option<&str>) -> Result<Vec<PathBuf>, String> {
+    check(root, day)?;
+    let workspace = root.parent().ok_or("the crate isn't in a workspace")?;
+    let settings = Settings::load(workspace)?;
+    let module = root.join(format!("src/day{day:02}.rs"));
+    let bin = root.join(format!("src/bin/day{day:02}.rs"));
+    let examples = workspace.join(settings.examples_path(day));
+    let (lib, solution) = (root.join("src/lib.rs"), root.join("src/solution.rs"));
+    let registered_lib = register_module(&read(&lib)?, day)?;
+    let registered_solution = register_day(&read(&solution)?, day)?;
+
+    let description = workspace.join(settings.description_path(day));
+    let title = match title {
+        Some(title) => title.to_string(),
+        None => read(&description)
+            .ok()
+            .and_then(|description| description_title(&description))
+            .unwrap_or_else(|| "Untitled".to_string()),
+    };
+
+    let mut written = vec![];
+    let mut write = |path: &Path, contents: String| {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("failed to create {}: {e}", dir.display()))?;
+        }
+        fs::write(path, contents)
+            .map_err(|e| format!("failed to write {}: {e}", path.display()))?;
+        written.push(path.to_path_buf());
+        Ok::<_, String>(())
+    };
+    write(&module, fill(MODULE, day, &title))?;
+    write(&bin, fill(BIN, day, &title))?;
+    if !examples.exists() {
+        write(&examples, EXAMPLES.to_string())?;
+    }
+    write(&lib, registered_lib)?;
+    write(&solution, registered_solution)?;
+    Ok(written)
+}
+
+/// Run rustfmt over the registry in the crate at `root`, in case the line
+/// of days has outgrown the line width.
+pub fn format(root: &Path) -> Result<(), String> {
+    let status = Command::new("rustfmt")
+        .args(["--edition", "2021"])
+        .arg(root.join("src/solution.rs"))
+        .status()
+        .map_err(|e| format!("failed to run rustfmt ({e}), is it installed?"))?;
+    if !status.success() {
+        return Err(format!("rustfmt failed: {status}"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn registering() {
+        let lib = "//! Code.\n\npub mod bench;\npub mod day01;\npub mod day03;\npub mod iter;\n";
+        assert_eq!(
+            register_module(lib, 2).unwrap(),
+            "//! Code.\n\npub mod bench;\npub mod day01;\npub mod day02;\npub mod day03;\npub mod iter;\n"
+        );
+        assert!(register_module(lib, 3).is_err());
+
+        let solution = "use crate::{\n    bench::Phase,\n    day01, day03,\n};\n\n\
+            pub const DAYS: &[Day] = &[\n    Day::new::<day01::Puzzle>(),\n    \
+            Day::new::<day03::Puzzle>().with_generator(day03::generate::sized),\n];\n";
+        insta::assert_snapshot!(register_day(solution, 2).unwrap(), @r"
+        use crate::{
+            bench::Phase,
+            day01, day02, day03,
+        };
+
+        pub const DAYS: &[Day] = &[
+            Day::new::<day01::Puzzle>(),
+            Day::new::<day02::Puzzle>(),
+            Day::new::<day03::Puzzle>().with_generator(day03::generate::sized),
+        ];
+        ");
+        insta::assert_snapshot!(register_day(solution, 4).unwrap(), @r"
+        use crate::{
+            bench::Phase,
+            day01, day03, day04,
+        };
+
+        pub const DAYS: &[Day] = &[
+            Day::new::<day01::Puzzle>(),
+            Day::new::<day03::Puzzle>().with_generator(day03::generate::sized),
+            Day::new::<day04::Puzzle>(),
+        ];
+        ");
+
+        let first = "use crate::{\n    bench::Phase,\n    day03,\n};\n\n\
+            pub const DAYS: &[Day] = &[\n    Day::new::<day03::Puzzle>(),\n];\n";
+        insta::assert_snapshot!(register_day(first, 1).unwrap(), @r"
+        use crate::{
+            bench::Phase,
+            day01, day03,
+        };
+
+        pub const DAYS: &[Day] = &[
+            Day::new::<day01::Puzzle>(),
+            Day::new::<day03::Puzzle>(),
+        ];
+        ");
+    }
+
+    #[test]
+    fn registering_imports() {
+        // the registry itself, as rustfmt left it
+        let solution = include_str!("solution.rs");
+        let registered = register_day(solution, 25).unwrap();
+        let imports = |solution: &str| {
+            let start = solution.find("use crate::{").unwrap();
+            solution[start..start + solution[start..].find("};").unwrap()].to_string()
+        };
+        assert_eq!(
+            imports(&registered),
+            imports(solution).replacen(" day03,", " day03, day25,", 1)
+        );
+
+        let nested = "use crate::{\n    a::{\n        B,\n        C,\n    },\n    day02,\n    \
+            z::{self, D},\n};\n\npub const DAYS: &[Day] = &[\n];\n";
+        insta::assert_snapshot!(register_day(nested, 1).unwrap(), @r"
+        use crate::{
+            a::{B, C},
+            day01, day02,
+            z::{self, D},
+        };
+
+        pub const DAYS: &[Day] = &[
+            Day::new::<day01::Puzzle>(),
+        ];
+        ");
+        assert_eq!(
+            import_entries(" a::{b, c},\n day01 , "),
+            ["a::{b, c}", "day01"]
+        );
+    }
+
+    #[test]
+    fn filling() {
+        let module = fill(MODULE, 7, r#"The "Quoted" \ Day"#);
+        assert!(module.starts_with("//! Day 7: The \"Quoted\" \\ Day.\n"));
+        assert!(module.contains(r#"const TITLE: &'static str = "The \"Quoted\" \\ Day";"#));
+        assert!(fill(BIN, 7, "").contains("day07::Puzzle"));
+    }
+
+    #[test]
+    fn titles() {
+        assert_eq!(
+            description_title("## --- Day 4: Ceres Search ---\n\nText").as_deref(),
+            Some("Ceres Search")
+        );
+        assert_eq!(
+            description_title("<div>\n\n## --- Day 1: Historian Hysteria ---").as_deref(),
+            Some("Historian Hysteria")
+        );
+        assert_eq!(description_title("no heading"), None);
+    }
+}
@@ -0,0 +1,162 @@
+//! The settings `fetch` reads from `aoc.toml` at the workspace root that say
+//! where things are saved, with the same defaults, so inputs, examples,
+//! descriptions and progress are found wherever `fetch` saved them.
+
+use serde::Deserialize;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+/// Paths are relative to the workspace root.
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub year: u16,
+    pub inputs: PathBuf,
+    pub descriptions: PathBuf,
+    /// File names, where `{year}`, `{day}` and `{day:02}` are replaced.
+    pub input_file: String,
+    pub description_file: String,
+    pub examples_file: String,
+    pub progress: PathBuf,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            year: 2024,
+            inputs: PathBuf::from("solutions/src/bin"),
+            descriptions: PathBuf::from("solutions/src/bin"),
+            input_file: "day{day:02}.input".to_string(),
+            description_file: "day{day:02}.md".to_string(),
+            examples_file: "day{day:02}.examples.toml".to_string(),
+            progress: PathBuf::from("progress.toml"),
+        }
+    }
+}
+
+/// `template` for `day` of `year`, filled in the same way as by `fetch`.
+fn fill(template: &str, year: u16, day: u8) -> String {
+    template
+        .replace("{year}", &year.to_string())
+        .replace("{day:02}", &format!("{day:02}"))
+        .replace("{day}", &day.to_string())
+}
+
+/// The workspace this crate is in.
+pub fn workspace() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("the crate is in a workspace")
+}
+
+/// The settings of this crate's workspace, loaded once.
+pub fn current() -> Result<&'static Settings, String> {
+    static SETTINGS: OnceLock<Result<Settings, String>> = OnceLock::new();
+    SETTINGS
+        .get_or_init(|| Settings::load(workspace()))
+        .as_ref()
+        .map_err(Clone::clone)
+}
+
+impl Settings {
+    /// The settings in `aoc.toml` at the root of `workspace`, or the defaults
+    /// if there isn't one, with `$AOC_YEAR` overriding the year as it does
+    /// for `fetch`.
+    pub fn load(workspace: &Path) -> Result<Self, String> {
+        let path = workspace.join("aoc.toml");
+        let mut settings = if path.exists() {
+            let toml = fs::read_to_string(&path)
+                .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+            toml::from_str(&toml).map_err(|e| format!("failed to parse {}: {e}", path.display()))?
+        } else {
+            Settings::default()
+        };
+        settings.override_with(|name| env::var(name).ok())?;
+        Ok(settings)
+    }
+
+    fn override_with(&mut self, var: impl Fn(&str) -> Option<String>) -> Result<(), String> {
+        if let Some(year) = var("AOC_YEAR") {
+            self.year = year
+                .parse()
+                .map_err(|e| format!("AOC_YEAR isn't a year: {e}"))?;
+        }
+        Ok(())
+    }
+
+    pub fn input_path(&self, day: u8) -> PathBuf {
+        self.inputs.join(fill(&self.input_file, self.year, day))
+    }
+
+    pub fn description_path(&self, day: u8) -> PathBuf {
+        self.descriptions
+            .join(fill(&self.description_file, self.year, day))
+    }
+
+    pub fn examples_path(&self, day: u8) -> PathBuf {
+        self.descriptions
+            .join(fill(&self.examples_file, self.year, day))
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct Case {
+        template: String,
+        year: u16,
+        day: u8,
+        filled: String,
+    }
+
+    #[derive(Deserialize)]
+    struct Cases {
+        case: Vec<Case>,
+    }
+
+    #[test]
+    fn filling() {
+        // the same cases as `fetch` is checked against
+        let cases: Cases = toml::from_str(include_str!("../../fetch/fixtures/fill.toml")).unwrap();
+        for case in cases.case {
+            assert_eq!(fill(&case.template, case.year, case.day), case.filled);
+        }
+    }
+
+    #[test]
+    fn loading() {
+        let workspace = env::temp_dir().join(format!("aoc-settings-{}", std::process::id()));
+        fs::create_dir_all(&workspace).unwrap();
+        let mut settings = Settings::load(&workspace).unwrap();
+        settings.override_with(|_| None).unwrap();
+        assert_eq!(
+            settings.input_path(3),
+            Path::new("solutions/src/bin/day03.input")
+        );
+        assert_eq!(
+            settings.examples_path(3),
+            Path::new("solutions/src/bin/day03.examples.toml")
+        );
+
+        // `fetch`'s other settings are left to it
+        fs::write(
+            workspace.join("aoc.toml"),
+            "year = 2023\ninputs = \"inputs\"\ninput_file = \"{year}/{day}.txt\"\n\
+             descriptions = \"docs\"\nexamples_file = \"{day}.toml\"\nrequest_interval = 10\n",
+        )
+        .unwrap();
+        let mut settings = Settings::load(&workspace).unwrap();
+        settings
+            .override_with(|name| (name == "AOC_YEAR").then(|| "2022".to_string()))
+            .unwrap();
+        assert_eq!(settings.input_path(3), Path::new("inputs/2022/3.txt"));
+        assert_eq!(settings.examples_path(3), Path::new("docs/3.toml"));
+        assert_eq!(settings.description_path(3), Path::new("docs/day03.md"));
+        fs::remove_dir_all(workspace).unwrap();
+    }
+}
@@ -0,0 +1,220 @@
+//! The common shape of a day's solution, so every day can be run and
+//! benchmarked the same way.
+
+use crate::{
+    bench::{Measurement, Phase, Sampler},
+    day01, day02, day03,
+    runner::Report,
+    settings,
+};
+use serde::Deserialize;
+use std::{
+    fmt::Display,
+    fs,
+    hint::black_box,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+/// A puzzle split into its phases: parsing the input once, then solving each
+/// part from the parsed input.
+pub trait Solution {
+    /// Day of December the puzzle was released.
+    const DAY: u8;
+    const TITLE: &'static str;
+    /// What both parts are solved from.
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, String>;
+    fn part1(input: &Self::Input) -> impl Display;
+    fn part2(input: &Self::Input) -> impl Display;
+}
+
+/// An example from the puzzle description, with the answers the description
+/// gives for it.
+#[derive(Debug, Deserialize)]
+pub struct Example {
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Examples {
+    #[serde(default)]
+    example: Vec<Example>,
+}
+
+/// A registered day, with its solution's type erased so days can be kept
+/// in a list.
+pub struct Day {
+    pub day: u8,
+    pub title: &'static str,
+    solve: fn(&str) -> Result<Report, String>,
+    bench: fn(&str, &Sampler) -> Result<Vec<Measurement>, String>,
+    generate: Option<fn(u64, usize) -> String>,
+}
+
+impl Day {
+    pub const fn new<S: Solution>() -> Self {
+        Day {
+            day: S::DAY,
+            title: S::TITLE,
+            solve: solve::<S>,
+            bench: bench::<S>,
+            generate: None,
+        }
+    }
+
+    /// Make up inputs with `generate`, called with a seed and a size in
+    /// whatever unit suits the day.
+    pub const fn with_generator(mut self, generate: fn(u64, usize) -> String) -> Self {
+        self.generate = Some(generate);
+        self
+    }
+
+    /// Where `fetch` saves the puzzle input, by `aoc.toml`.
+    pub fn input_path(&self) -> Result<PathBuf, String> {
+        let settings = settings::current()?;
+        Ok(settings::workspace().join(settings.input_path(self.day)))
+    }
+
+    pub fn read_input(&self) -> Result<String, String> {
+        read(&self.input_path()?)
+    }
+
+    /// Where `fetch` saves the examples from the puzzle description, by
+    /// `aoc.toml`.
+    pub fn examples_path(&self) -> Result<PathBuf, String> {
+        let settings = settings::current()?;
+        Ok(settings::workspace().join(settings.examples_path(self.day)))
+    }
+
+    /// The examples from the puzzle description, or none if they haven't
+    /// been fetched.
+    pub fn examples(&self) -> Result<Vec<Example>, String> {
+        let path = self.examples_path()?;
+        if !path.exists() {
+            return Ok(vec![]);
+        }
+        let examples: Examples = toml::from_str(&read(&path)?)
+            .map_err(|e| format!("failed to parse {}: {e}", path.display()))?;
+        Ok(examples.example)
+    }
+
+    /// Answers to both parts, timing each phase.
+    pub fn solve(&self, input: &str) -> Result<Report, String> {
+        (self.solve)(input)
+    }
+
+    /// Read the input from `path`, or the puzzle input by default, and solve
+    /// it, timing the reading too.
+    pub fn run(&self, path: Option<&Path>) -> Result<Report, String> {
+        let start = Instant::now();
+        let input = match path {
+            Some(path) => read(path)?,
+            None => self.read_input()?,
+        };
+        let read = start.elapsed();
+        let mut report = self.solve(&input)?;
+        report.timings.insert(0, (Phase::Read, read));
+        Ok(report)
+    }
+
+    /// A made up input of roughly `size`, the same for the same `seed`.
+    pub fn generate(&self, seed: u64, size: usize) -> Result<String, String> {
+        let generate = self
+            .generate
+            .ok_or_else(|| format!("day {} has no input generator", self.day))?;
+        Ok(generate(seed, size))
+    }
+
+    /// Time each phase of solving `input`.
+    pub fn bench(&self, input: &str, sampler: &Sampler) -> Result<Vec<Measurement>, String> {
+        (self.bench)(input, sampler)
+    }
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("failed to read {}: {e}", path.display()))
+}
+
+fn solve<S: Solution>(input: &str) -> Result<Report, String> {
+    let start = Instant::now();
+    let input = S::parse(input)?;
+    let parse = start.elapsed();
+
+    let start = Instant::now();
+    let part1 = S::part1(&input).to_string();
+    let part1_time = start.elapsed();
+
+    let start = Instant::now();
+    let part2 = S::part2(&input).to_string();
+    let part2_time = start.elapsed();
+
+    Ok(Report {
+        day: S::DAY,
+        title: S::TITLE,
+        answers: [part1, part2],
+        timings: vec![
+            (Phase::Parse, parse),
+            (Phase::Part1, part1_time),
+            (Phase::Part2, part2_time),
+        ],
+    })
+}
+
+fn bench<S: Solution>(input: &str, sampler: &Sampler) -> Result<Vec<Measurement>, String> {
+    let parsed = S::parse(input)?;
+    let measurement = |phase, stats| Measurement {
+        day: S::DAY,
+        phase,
+        stats,
+    };
+    Ok(vec![
+        measurement(Phase::Parse, sampler.measure(|| S::parse(black_box(input)))),
+        measurement(
+            Phase::Part1,
+            sampler.measure(|| S::part1(black_box(&parsed))),
+        ),
+        measurement(
+            Phase::Part2,
+            sampler.measure(|| S::part2(black_box(&parsed))),
+        ),
+    ])
+}
+
+/// Every solved day, in order.
+pub const DAYS: &[Day] = &[
+    Day::new::<day01::Puzzle>().with_generator(day01::generate::sized),
+    Day::new::<day02::Puzzle>().with_generator(day02::generate::sized),
+    Day::new::<day03::Puzzle>().with_generator(day03::generate::sized),
+];
+
+pub fn find(day: u8) -> Result<&'static Day, String> {
+    DAYS.iter()
+        .find(|d| d.day == day)
+        .ok_or_else(|| format!("day {day} hasn't been solved"))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn registered_in_order() {
+        // days can be skipped, but not repeated or out of order
+        assert!(DAYS.windows(2).all(|pair| pair[0].day < pair[1].day));
+        assert!(DAYS.iter().all(|d| (1..=25).contains(&d.day)));
+        assert!(find(0).is_err());
+    }
+
+    #[test]
+    fn generated_inputs_solve() {
+        // a new day has no generator until one's written for it
+        for day in DAYS.iter().filter(|day| day.generate.is_some()) {
+            let input = day.generate(day.day as u64, 100).unwrap();
+            day.solve(&input).unwrap();
+        }
+    }
+}
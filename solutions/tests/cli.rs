@@ -0,0 +1,69 @@
+//! Snapshots of what the binaries print, on generated inputs so they don't
+//! depend on anyone's puzzle input.
+
+use std::{env, fs, path::PathBuf, process::Command};
+
+/// Run `bin` with `args`, returning what it printed minus the timing lines,
+/// which change from run to run.
+fn run(bin: &str, args: &[&str]) -> String {
+    let output = Command::new(bin).args(args).output().unwrap();
+    assert!(
+        output.status.success(),
+        "{bin} {args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout
+        .lines()
+        .filter(|line| !line.starts_with("time:"))
+        .collect();
+    lines.join("\n")
+}
+
+/// Generate an input for `day` into a file only this test uses.
+fn generated(day: &str, args: &[&str]) -> PathBuf {
+    let path = env::temp_dir().join(format!("aoc-cli-{}-{day}.input", std::process::id()));
+    let mut all = vec!["generate", day, "--output", path.to_str().unwrap()];
+    all.extend(args);
+    run(env!("CARGO_BIN_EXE_aoc"), &all);
+    path
+}
+
+#[test]
+fn generate() {
+    let aoc = env!("CARGO_BIN_EXE_aoc");
+    insta::assert_snapshot!(
+        "generate_day01",
+        run(aoc, &["generate", "day01", "--lines", "5"])
+    );
+    insta::assert_snapshot!(
+        "generate_day02",
+        run(aoc, &["generate", "day02", "--reports", "5"])
+    );
+    insta::assert_snapshot!(
+        "generate_day03",
+        run(aoc, &["generate", "day03", "--length", "120"])
+    );
+}
+
+#[test]
+fn day01() {
+    let input = generated("day01", &["--lines", "100"]);
+    let output = run(
+        env!("CARGO_BIN_EXE_day01"),
+        &["--input", input.to_str().unwrap()],
+    );
+    fs::remove_file(input).unwrap();
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn day02_explain() {
+    let input = generated("day02", &["--reports", "12"]);
+    let output = run(
+        env!("CARGO_BIN_EXE_day02"),
+        &["--input", input.to_str().unwrap(), "--explain"],
+    );
+    fs::remove_file(input).unwrap();
+    insta::assert_snapshot!(output);
+}
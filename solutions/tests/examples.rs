@@ -0,0 +1,33 @@
+//! Every day against the examples in its puzzle description, so a day has
+//! tests as soon as it's fetched.
+
+use advent_of_code_2024::solution::DAYS;
+
+#[test]
+fn examples() {
+    let mut failures = vec![];
+    for day in DAYS {
+        for (idx, example) in day.examples().unwrap().iter().enumerate() {
+            let name = format!("day {} example {}", day.day, idx + 1);
+            let report = match day.solve(&example.input) {
+                Ok(report) => report,
+                Err(e) => {
+                    failures.push(format!("{name}: {e}"));
+                    continue;
+                }
+            };
+            let expected = [&example.part1, &example.part2];
+            for (part, (expected, answer)) in expected.iter().zip(&report.answers).enumerate() {
+                if let Some(expected) = expected {
+                    if expected != answer {
+                        failures.push(format!(
+                            "{name} part {}: expected {expected}, got {answer}",
+                            part + 1
+                        ));
+                    }
+                }
+            }
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
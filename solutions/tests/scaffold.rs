@@ -0,0 +1,73 @@
+//! Scaffolding a day into a copy of the workspace, then checking the copy
+//! still builds and its registry tests pass with the new day in it.
+
+use advent_of_code_2024::scaffold;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// What building the copy needs from the workspace: the manifests and the
+/// sources, but not the inputs, descriptions or benchmark results.
+const COPIED: &[&str] = &[
+    "Cargo.toml",
+    "Cargo.lock",
+    "solutions/Cargo.toml",
+    "solutions/src",
+    "solutions/benches",
+    "fetch/Cargo.toml",
+    "fetch/src",
+    "fetch/fixtures",
+];
+
+/// Copy `from` to `to`, leaving out saved puzzles, and directories under
+/// `from` when it's `benches`, which only has results in them.
+fn copy(from: &Path, to: &Path) {
+    if from.is_file() {
+        fs::create_dir_all(to.parent().unwrap()).unwrap();
+        fs::copy(from, to).unwrap();
+        return;
+    }
+    for entry in fs::read_dir(from).unwrap() {
+        let path = entry.unwrap().path();
+        let saved = path.extension().is_some_and(|extension| {
+            ["input", "md", "html"]
+                .iter()
+                .any(|saved| extension == *saved)
+        });
+        if path.is_dir() && from.ends_with("benches") || saved {
+            continue;
+        }
+        copy(&path, &to.join(path.file_name().unwrap()));
+    }
+}
+
+#[test]
+fn scaffolded_day_registers() {
+    let tmp = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    let workspace = tmp.join(format!("scaffold-{}", std::process::id()));
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    for path in COPIED.iter().filter(|path| root.join(path).exists()) {
+        copy(&root.join(path), &workspace.join(path));
+    }
+
+    // a day after a gap, without a generator, titled with what needs escaping
+    let crate_root = workspace.join("solutions");
+    scaffold::scaffold(&crate_root, 5, Some(r#"Print "Queue" \ Day"#)).unwrap();
+
+    // built alongside everything else, so only the copied crate is rebuilt
+    let output = Command::new(env!("CARGO"))
+        .args(["test", "--offline", "--lib", "solution::tests"])
+        .current_dir(&crate_root)
+        .env("CARGO_TARGET_DIR", tmp.parent().unwrap())
+        .output()
+        .unwrap();
+    fs::remove_dir_all(&workspace).unwrap();
+    assert!(
+        output.status.success(),
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}